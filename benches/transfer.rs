@@ -0,0 +1,70 @@
+// Copyright (c) 2020-2024 MicroDoc Software GmbH.
+// See the "LICENSE.txt" file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license. This file may not be copied, modified,
+// or distributed except according to those terms.
+
+/*! Benchmarks of the splitting of address ranges, and of data transfers. */
+
+use std::hint::black_box;
+use std::io::{Read, Seek, SeekFrom};
+use std::process;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use process_vm_io::ProcessVirtualMemoryIO;
+
+fn page_size() -> u64 {
+    process_vm_io::system_page_size().unwrap().get()
+}
+
+fn iov_max() -> u64 {
+    process_vm_io::system_iov_max().get() as u64
+}
+
+/// Sizes of address ranges, each starting in the middle of a page.
+fn range_sizes() -> [(&'static str, u64); 4] {
+    let page_size = page_size();
+    [
+        ("one_byte", 1),
+        ("one_page", page_size),
+        ("100_pages", 100 * page_size),
+        ("near_iov_max", (iov_max() - 1) * page_size),
+    ]
+}
+
+fn split_address_range(c: &mut Criterion) {
+    let io = unsafe { ProcessVirtualMemoryIO::new(process::id(), 0) }.unwrap();
+    let start = 0x1000_0000 + page_size() / 2;
+
+    let mut group = c.benchmark_group("plan_transfer");
+    for (name, size) in range_sizes() {
+        group.bench_with_input(BenchmarkId::from_parameter(name), &size, |b, &size| {
+            b.iter(|| io.plan_transfer(black_box(start..start + size)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn read_current_process(c: &mut Criterion) {
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process::id(), 0) }.unwrap();
+
+    let mut group = c.benchmark_group("read");
+    for (name, size) in range_sizes() {
+        let size = size as usize;
+        let source = vec![0xa5_u8; size + page_size() as usize];
+        let address = source[page_size() as usize / 2..].as_ptr() as u64;
+        let mut buffer = vec![0_u8; size];
+
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_function(BenchmarkId::from_parameter(name), |b| {
+            b.iter(|| {
+                io.seek(SeekFrom::Start(address)).unwrap();
+                io.read(black_box(&mut buffer)).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, split_address_range, read_current_process);
+criterion_main!(benches);
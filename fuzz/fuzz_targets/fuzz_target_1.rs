@@ -6,7 +6,7 @@
 
 #![no_main]
 
-use std::io::{Read, Seek, SeekFrom, Write};
+use std::io::{IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
 use std::{cmp, process};
 
 use libfuzzer_sys::arbitrary::Arbitrary;
@@ -22,6 +22,7 @@ struct FuzzInput {
     position2: SeekFrom,
     position3: SeekFrom,
     io_size: usize,
+    targets: Vec<(u64, usize)>,
 }
 
 impl Arbitrary for SeekFromWrapper {
@@ -45,13 +46,20 @@ impl Arbitrary for FuzzInput {
             position2: SeekFromWrapper::arbitrary(u)?.0,
             position3: SeekFromWrapper::arbitrary(u)?.0,
             io_size: cmp::min(usize::arbitrary(u)?, 10 * 1024 * 1024),
+            targets: (0..u8::arbitrary(u)? % 8)
+                .map(|_| {
+                    let address = u64::arbitrary(u)?;
+                    let len = cmp::min(usize::arbitrary(u)?, 1024 * 1024);
+                    Ok::<_, U::Error>((address, len))
+                })
+                .collect::<Result<_, _>>()?,
         })
     }
 }
 
 fuzz_target!(|data: FuzzInput| {
     let mut io = unsafe {
-        ProcessVirtualMemoryIO::new(process::id() as libc::pid_t, data.initial_address).unwrap()
+        ProcessVirtualMemoryIO::new(process::id(), data.initial_address).unwrap()
     };
 
     let _ = io.seek(data.position1);
@@ -69,4 +77,112 @@ fuzz_target!(|data: FuzzInput| {
 
         assert_eq!(read_buf[..read_count], *write_buf.as_slice());
     }
+
+    // Positioned reads at arbitrary addresses, and writes of the data just
+    // read back to where it was read.
+    for &(address, len) in &data.targets {
+        let mut buf = vec![0_u8; len];
+        let _ = io.read_large(&mut buf, address);
+
+        if let Ok(count) = io.read_at(address, &mut buf) {
+            let _ = io.write_at(address, &buf[..count]);
+        }
+    }
+
+    let source: Vec<u8> = (0..data.io_size).map(|i| i as u8).collect();
+
+    // Scattered round trip through buffers of this process, over the
+    // arbitrary targets clamped into them.
+    if !source.is_empty() {
+        let ranges: Vec<(usize, usize)> = data
+            .targets
+            .iter()
+            .map(|&(address, len)| {
+                let offset = (address % source.len() as u64) as usize;
+                (offset, cmp::min(len, source.len() - offset))
+            })
+            .collect();
+        let remote_at = |address: u64| -> Vec<(u64, usize)> {
+            ranges
+                .iter()
+                .map(|&(offset, len)| (address + offset as u64, len))
+                .collect()
+        };
+        let expected: Vec<u8> = ranges
+            .iter()
+            .flat_map(|&(offset, len)| &source[offset..offset + len])
+            .copied()
+            .collect();
+
+        let mut scattered_buf = vec![0_u8; expected.len()];
+        let read_count = io
+            .read_scattered(
+                &remote_at(source.as_ptr() as u64),
+                &mut [IoSliceMut::new(&mut scattered_buf)],
+            )
+            .unwrap_or(0);
+        assert_eq!(scattered_buf[..read_count], expected[..read_count]);
+
+        if read_count == expected.len() {
+            let mut scattered_destination = vec![0_u8; source.len()];
+            let write_count = io
+                .write_scattered(
+                    &remote_at(scattered_destination.as_mut_ptr() as u64),
+                    &[IoSlice::new(&scattered_buf)],
+                )
+                .unwrap();
+            assert_eq!(write_count, expected.len());
+            for &(offset, len) in &ranges {
+                assert_eq!(
+                    scattered_destination[offset..offset + len],
+                    source[offset..offset + len]
+                );
+            }
+        }
+    }
+
+    // Vectored round trip through buffers of this process, split according
+    // to the arbitrary lengths of the targets.
+    let mut destination = vec![0_u8; data.io_size];
+    let lengths: Vec<usize> = data
+        .targets
+        .iter()
+        .map(|&(_, len)| cmp::min(len, data.io_size))
+        .collect();
+
+    let mut vectored_buf = vec![0_u8; lengths.iter().sum()];
+    let vectored_count = {
+        let mut slices = Vec::new();
+        let mut rest = vectored_buf.as_mut_slice();
+        for &len in &lengths {
+            let (slice, tail) = rest.split_at_mut(len);
+            slices.push(IoSliceMut::new(slice));
+            rest = tail;
+        }
+
+        io.seek(SeekFrom::Start(source.as_ptr() as u64)).unwrap();
+        io.read_vectored(&mut slices).unwrap_or(0)
+    };
+    let vectored_count = cmp::min(vectored_count, data.io_size);
+    assert_eq!(vectored_buf[..vectored_count], source[..vectored_count]);
+
+    if vectored_count != 0 {
+        let slices: Vec<IoSlice> = vectored_buf[..vectored_count]
+            .chunks(cmp::max(1, vectored_count / cmp::max(1, lengths.len())))
+            .map(IoSlice::new)
+            .collect();
+
+        io.seek(SeekFrom::Start(destination.as_mut_ptr() as u64))
+            .unwrap();
+        let write_count = io.write_vectored(&slices).unwrap();
+        assert_eq!(destination[..write_count], source[..write_count]);
+
+        // Positioned compare-and-swap over the written prefix.
+        let expected = destination[..write_count].to_vec();
+        let new = vec![0xff_u8; write_count];
+        assert!(io
+            .write_if_equal(destination.as_ptr() as u64, &expected, &new)
+            .unwrap());
+        assert_eq!(destination[..write_count], *new.as_slice());
+    }
 });
@@ -0,0 +1,63 @@
+// Copyright (c) 2020-2024 MicroDoc Software GmbH.
+// See the "LICENSE.txt" file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license. This file may not be copied, modified,
+// or distributed except according to those terms.
+
+/*! Reading of bounded address ranges of the virtual memory of a process. */
+
+use std::ffi::c_void;
+use std::io::Read;
+use std::ops::Range;
+use std::{cmp, io};
+
+use crate::{process_vm_readv, ProcessVirtualMemoryIO};
+
+/// Reader of the data stored in an address range of the virtual memory of a
+/// process, as returned by [`ProcessVirtualMemoryIO::readable_regions`].
+///
+/// Reading starts at the start of the address range, and reaches the end of
+/// file at the end of the address range.
+#[derive(Debug)]
+pub struct BoundedReader<'a> {
+    io: &'a ProcessVirtualMemoryIO,
+
+    /// Remaining address range to read.
+    range: Range<u64>,
+}
+
+impl<'a> BoundedReader<'a> {
+    pub(crate) fn new(io: &'a ProcessVirtualMemoryIO, range: Range<u64>) -> Self {
+        Self { io, range }
+    }
+
+    /// Return the remaining address range to read.
+    pub fn remaining(&self) -> Range<u64> {
+        self.range.clone()
+    }
+}
+
+impl Read for BoundedReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.range.end.saturating_sub(self.range.start);
+        let size = cmp::min(buf.len() as u64, remaining) as usize;
+
+        let local_io_vector = libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut c_void,
+            iov_len: size,
+        };
+
+        let count = self
+            .io
+            .io_vectored_at(
+                process_vm_readv,
+                self.range.start,
+                &[local_io_vector],
+                size as u64,
+            )
+            .map_err(io::Error::from)?;
+
+        self.range.start += count as u64;
+        Ok(count)
+    }
+}
@@ -0,0 +1,274 @@
+// Copyright (c) 2020-2024 MicroDoc Software GmbH.
+// See the "LICENSE.txt" file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license. This file may not be copied, modified,
+// or distributed except according to those terms.
+
+/*! Configurable construction of [`ProcessVirtualMemoryIO`] instances. */
+
+use std::io;
+use std::num::NonZeroU64;
+use std::sync::{Arc, OnceLock};
+
+use crate::{
+    procfs, Backend, BufferPool, Endianness, Error, PointerWidth, ProcessVirtualMemoryIO, Result,
+};
+
+/// Builder of [`ProcessVirtualMemoryIO`] instances, as returned by
+/// [`ProcessVirtualMemoryIO::builder`].
+#[derive(Debug, Clone)]
+pub struct Builder {
+    process_id: u32,
+    initial_address: u64,
+    skip_liveness_on_new: bool,
+    initial_maps_cache: bool,
+    target_page_size: Option<NonZeroU64>,
+    allow_self_stack: bool,
+    invalid_address_errors: bool,
+    auto_continue: bool,
+    check_writability: bool,
+    backend: Backend,
+    endianness: Endianness,
+    pointer_width: PointerWidth,
+    max_hole_skip: Option<u64>,
+    buffer_pool: Option<Arc<BufferPool>>,
+}
+
+impl Builder {
+    pub(crate) fn new(process_id: u32) -> Self {
+        Self {
+            process_id,
+            initial_address: 0,
+            skip_liveness_on_new: false,
+            initial_maps_cache: false,
+            target_page_size: None,
+            allow_self_stack: false,
+            invalid_address_errors: false,
+            auto_continue: false,
+            check_writability: false,
+            backend: Backend::Syscall,
+            endianness: Endianness::native(),
+            pointer_width: PointerWidth::native(),
+            max_hole_skip: None,
+            buffer_pool: None,
+        }
+    }
+
+    /// Set the virtual memory address where data transfers initially happen.
+    /// This defaults to zero.
+    pub fn initial_address(mut self, initial_address: u64) -> Self {
+        self.initial_address = initial_address;
+        self
+    }
+
+    /// Skip checking that the target process exists when building the
+    /// instance. This defaults to `false`.
+    ///
+    /// The check sends the null signal to the target process, which might be
+    /// denied in sandboxed environments even when the virtual memory of the
+    /// process is accessible. When the check is skipped, errors are reported
+    /// by the first data transfer instead.
+    pub fn skip_liveness_on_new(mut self, skip: bool) -> Self {
+        self.skip_liveness_on_new = skip;
+        self
+    }
+
+    /// Read and cache the memory regions of the target process when building
+    /// the instance. This defaults to `false`, in which case they are read
+    /// by the first query that needs them.
+    ///
+    /// This moves the cost of reading the memory regions out of the first
+    /// query, e.g., [`ProcessVirtualMemoryIO::region_containing`].
+    pub fn initial_maps_cache(mut self, enable: bool) -> Self {
+        self.initial_maps_cache = enable;
+        self
+    }
+
+    /// Set the size in bytes of the pages of the target process, which must be
+    /// a power of two. This defaults to the page size of the current system,
+    /// as returned by [`system_page_size()`](crate::system_page_size).
+    ///
+    /// Data transfers are split at boundaries of pages of the target process,
+    /// so that a transfer stops at the first page that is not accessible.
+    /// The target page size differs from the host page size when the data of
+    /// the target process is provided by other means than the host kernel,
+    /// e.g., by a checkpoint image of a process running on another system.
+    ///
+    /// A target page size larger than the host page size makes short
+    /// transfers coarser: a transfer might stop at a boundary of a target
+    /// page, before an inaccessible host page in the middle of that
+    /// target page. A smaller target page size only costs more `iovec`s.
+    pub fn target_page_size(mut self, page_size: NonZeroU64) -> Self {
+        self.target_page_size = Some(page_size);
+        self
+    }
+
+    /// Allow transferring data from/to the stack of the calling thread, when
+    /// the target process is the current process. This defaults to `false`.
+    ///
+    /// That stack changes as the calling thread runs, including during data
+    /// transfers, which makes such transfers racy. This is almost always a
+    /// mistake, so, in debug builds, such transfers are flagged by
+    /// [`TraceEvent::in_caller_stack`](crate::TraceEvent::in_caller_stack),
+    /// for the hook set by
+    /// [`ProcessVirtualMemoryIO::set_trace_hook`] to report them, unless
+    /// allowed. Release builds do not check.
+    pub fn allow_self_stack(mut self, allow: bool) -> Self {
+        self.allow_self_stack = allow;
+        self
+    }
+
+    /// Report data transfers that fail at their very first byte with an
+    /// `EFAULT` errno code by an error of kind
+    /// [`ErrorKind::InvalidAddress`](crate::ErrorKind::InvalidAddress),
+    /// carrying the address where the transfer started. This defaults to
+    /// `false`, in which case such errors are reported as is.
+    ///
+    /// When addresses are supplied by users, e.g., typed in an interactive
+    /// tool, such a fault usually means that the address is wrong, which
+    /// deserves a clearer error than an errno code. Faults that happen after
+    /// some data was transferred indicate a gap in the transferred address
+    /// range instead, and are always reported as is.
+    ///
+    /// [`Error::os_error_code`] still returns `EFAULT` for such errors.
+    pub fn invalid_address_errors(mut self, enable: bool) -> Self {
+        self.invalid_address_errors = enable;
+        self
+    }
+
+    /// Make reads continue when one system call transfers less than requested
+    /// only because of its limits. This defaults to `false`.
+    ///
+    /// See [`ProcessVirtualMemoryIO::set_auto_continue`].
+    pub fn auto_continue(mut self, enable: bool) -> Self {
+        self.auto_continue = enable;
+        self
+    }
+
+    /// Check that the memory region where a write starts is writable, before
+    /// writing. This defaults to `false`.
+    ///
+    /// See [`ProcessVirtualMemoryIO::set_check_writability`].
+    pub fn check_writability(mut self, enable: bool) -> Self {
+        self.check_writability = enable;
+        self
+    }
+
+    /// Set the backend that data transfers use. This defaults to
+    /// [`Backend::Syscall`], which falls back to [`Backend::ProcMem`] if the
+    /// system does not support `process_vm_readv()` and `process_vm_writev()`.
+    ///
+    /// Selecting [`Backend::ProcMem`] uses the `/proc/[pid]/mem` file of the
+    /// target process from the first data transfer, e.g., when the system
+    /// calls are known to be rejected, or to write to memory regions mapped
+    /// without write permission, which that file permits to privileged
+    /// processes.
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+
+    /// Set the byte order of values stored in the virtual memory of the target
+    /// process. This defaults to the byte order of the current process.
+    ///
+    /// See [`ProcessVirtualMemoryIO::set_endianness`].
+    pub fn endianness(mut self, endianness: Endianness) -> Self {
+        self.endianness = endianness;
+        self
+    }
+
+    /// Set the size of pointers stored in the virtual memory of the target
+    /// process. This defaults to the size of pointers of the current process.
+    ///
+    /// See [`ProcessVirtualMemoryIO::set_pointer_width`].
+    pub fn pointer_width(mut self, pointer_width: PointerWidth) -> Self {
+        self.pointer_width = pointer_width;
+        self
+    }
+
+    /// Make reads skip unreadable address ranges of up to `max_skip` bytes.
+    /// By default, such ranges are not skipped.
+    ///
+    /// See [`ProcessVirtualMemoryIO::set_auto_skip_holes`].
+    pub fn auto_skip_holes(mut self, max_skip: u64) -> Self {
+        self.max_hole_skip = Some(max_skip);
+        self
+    }
+
+    /// Draw scratch buffers from `pool` instead of allocating them on each
+    /// call. By default, there is no pool.
+    ///
+    /// See [`ProcessVirtualMemoryIO::set_buffer_pool`].
+    pub fn buffer_pool(mut self, pool: Arc<BufferPool>) -> Self {
+        self.buffer_pool = Some(pool);
+        self
+    }
+
+    /// Create a new object to perform input/output of data from/to the virtual
+    /// memory contents of the target process.
+    ///
+    /// If the target page size is not a power of two, then an error of kind
+    /// [`InvalidInput`](io::ErrorKind::InvalidInput) is returned.
+    ///
+    /// See [`ProcessVirtualMemoryIO::new`] for details.
+    ///
+    /// # Safety
+    ///
+    /// Writing to the virtual memory of a process is a potentially unsafe
+    /// operation because it may introduce memory unsafety in that process,
+    /// and may lead to unexpected states in that process. This is even more
+    /// dangerous when the target process is the currently running process.
+    pub unsafe fn build(self) -> Result<ProcessVirtualMemoryIO> {
+        let process_id = self.process_id as libc::pid_t;
+        if process_id <= 0 {
+            return Err(Error::from_io3(
+                io::ErrorKind::InvalidInput.into(),
+                "process_vm_io::ProcessVirtualMemoryIO::new",
+                process_id,
+            ));
+        }
+
+        if let Some(page_size) = self.target_page_size {
+            if !page_size.is_power_of_two() {
+                return Err(Error::from_io3(
+                    io::ErrorKind::InvalidInput.into(),
+                    "process_vm_io::ProcessVirtualMemoryIO::new",
+                    process_id,
+                ));
+            }
+        }
+
+        if !self.skip_liveness_on_new {
+            ProcessVirtualMemoryIO::ensure_process_exists(process_id)?;
+        }
+
+        let mut result =
+            unsafe { ProcessVirtualMemoryIO::new_unchecked(self.process_id, self.initial_address) };
+
+        if !self.skip_liveness_on_new {
+            // The start time is only needed by `revalidate()`, which can
+            // record it later, e.g., if `/proc` is not accessible yet.
+            if let Ok(start_time) = procfs::start_time(process_id) {
+                result.start_time = OnceLock::from(start_time);
+            }
+        }
+
+        if let Some(page_size) = self.target_page_size {
+            result.page_size = page_size.get();
+        }
+        result.allow_self_stack = self.allow_self_stack;
+        result.invalid_address_errors = self.invalid_address_errors;
+        result.auto_continue = self.auto_continue;
+        result.check_writability = self.check_writability;
+        result.endianness = self.endianness;
+        result.pointer_width = self.pointer_width;
+        result.max_hole_skip = self.max_hole_skip;
+        result.buffer_pool = self.buffer_pool;
+        *result.proc_mem_fallback.get_mut() = self.backend == Backend::ProcMem;
+
+        if self.initial_maps_cache {
+            result.refresh_maps()?;
+        }
+        Ok(result)
+    }
+}
@@ -0,0 +1,307 @@
+// Copyright (c) 2020-2024 MicroDoc Software GmbH.
+// See the "LICENSE.txt" file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license. This file may not be copied, modified,
+// or distributed except according to those terms.
+
+/*! Reading of the virtual memory of a process from a CRIU checkpoint image. */
+
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::Range;
+use std::os::unix::fs::FileExt;
+use std::path::Path;
+use std::{cmp, io};
+
+use crate::{seek_address, Error, Result, MIN_SYSTEM_PAGE_SIZE};
+
+/// Magic number starting most CRIU image files.
+const IMG_COMMON_MAGIC: u32 = 0x5456_4319;
+
+/// Magic number identifying a CRIU `pagemap` image file.
+const PAGEMAP_MAGIC: u32 = 0x5608_4025;
+
+/// Flag of a `pagemap` entry whose pages are stored in the parent checkpoint.
+const PE_PARENT: u64 = 1 << 0;
+
+/// Flag of a `pagemap` entry whose pages are stored in the `pages` image file.
+const PE_PRESENT: u64 = 1 << 2;
+
+/// Address range of the virtual memory of the checkpointed process whose data
+/// is stored in the `pages` image file.
+#[derive(Debug)]
+struct PagemapEntry {
+    address: u64,
+    size: u64,
+    /// Offset of the data in the `pages` image file.
+    offset: u64,
+}
+
+/// Input object reading the virtual memory contents of a process from a
+/// checkpoint image created by [CRIU](https://criu.org), e.g., for
+/// post-mortem analysis.
+///
+/// The virtual memory is reconstructed from the `pagemap-<pid>.img` and
+/// `pages-<id>.img` image files. Like [`ProcessVirtualMemoryIO`], this object
+/// implements [`Read`] and [`Seek`] over the address space of the process,
+/// and reading at an address whose data is not stored in the checkpoint fails
+/// like reading unmapped memory, i.e., with an `EFAULT` errno code.
+///
+/// Pages are assumed to have the size of the pages of the current system, as
+/// CRIU checkpoints are restored on systems similar to the checkpointed one.
+/// Pages stored in a parent checkpoint of an incremental checkpoint, or
+/// left for lazy restoration, are not available.
+///
+/// [`ProcessVirtualMemoryIO`]: crate::ProcessVirtualMemoryIO
+#[derive(Debug)]
+pub struct CheckpointImage {
+    process_id: libc::pid_t,
+
+    /// The `pages` image file.
+    pages: File,
+
+    /// Address ranges whose data is stored in `pages`, sorted by address.
+    entries: Vec<PagemapEntry>,
+
+    /// Current virtual memory address where reading happens.
+    /// A value of `None` means we are **past** the end of the address space.
+    address: Option<u64>,
+}
+
+impl CheckpointImage {
+    /// Open the checkpoint image of the process identified by `process_id`,
+    /// stored in `directory`, with an initial address of zero.
+    ///
+    /// The process identifier is the one recorded in the checkpoint, which
+    /// names its `pagemap-<pid>.img` image file. If the image files are
+    /// malformed, then an error of kind
+    /// [`InvalidData`](io::ErrorKind::InvalidData) is returned.
+    pub fn open(directory: impl AsRef<Path>, process_id: u32) -> Result<Self> {
+        const OPERATION: &str = "process_vm_io::CheckpointImage::open";
+
+        let directory = directory.as_ref();
+        let pid = process_id as libc::pid_t;
+
+        let pagemap = fs::read(directory.join(format!("pagemap-{process_id}.img")))
+            .map_err(|err| Error::from_io3(err, "read(pagemap-[pid].img)", pid))?;
+
+        let (pages_id, entries) = parse_pagemap(&pagemap, *MIN_SYSTEM_PAGE_SIZE)
+            .ok_or_else(|| Error::from_io3(io::ErrorKind::InvalidData.into(), OPERATION, pid))?;
+
+        let pages = File::open(directory.join(format!("pages-{pages_id}.img")))
+            .map_err(|err| Error::from_io3(err, "open(pages-[id].img)", pid))?;
+
+        let size_of_pages = entries.iter().map(|entry| entry.size).sum::<u64>();
+        let file_size = pages
+            .metadata()
+            .map_err(|err| Error::from_io3(err, "fstat(pages-[id].img)", pid))?
+            .len();
+        if file_size < size_of_pages {
+            return Err(Error::from_io3(
+                io::ErrorKind::InvalidData.into(),
+                OPERATION,
+                pid,
+            ));
+        }
+
+        Ok(Self {
+            process_id: pid,
+            pages,
+            entries,
+            address: Some(0),
+        })
+    }
+
+    /// Return the process identifier recorded in the checkpoint.
+    pub fn process_id(&self) -> u32 {
+        self.process_id as u32
+    }
+
+    /// Return the current address, or `None` if the cursor is after the end of
+    /// the address space.
+    ///
+    /// See [`ProcessVirtualMemoryIO::address`](crate::ProcessVirtualMemoryIO::address).
+    pub fn address(&self) -> Option<u64> {
+        self.address
+    }
+
+    /// Set the current address to `address`.
+    pub fn set_address(&mut self, address: u64) {
+        self.address = Some(address);
+    }
+
+    /// Return the address ranges whose data is stored in the checkpoint,
+    /// sorted by address.
+    pub fn present_ranges(&self) -> impl Iterator<Item = Range<u64>> + '_ {
+        self.entries
+            .iter()
+            .map(|entry| entry.address..entry.address + entry.size)
+    }
+
+    /// Read data from the checkpointed virtual memory at the current address
+    /// into `buf`, then advance the current address by the number of bytes
+    /// read.
+    ///
+    /// This behaves like [`Read::read`], but returns an [`Error`] instead of an
+    /// [`io::Error`]. Reading stops at the end of the address range stored in
+    /// the checkpoint that contains the current address.
+    pub fn read_raw(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let address = match self.address {
+            Some(address) if !buf.is_empty() => address,
+            _ => return Ok(0),
+        };
+
+        let index = self
+            .entries
+            .partition_point(|entry| entry.address + entry.size <= address);
+
+        let entry = self
+            .entries
+            .get(index)
+            .filter(|entry| entry.address <= address)
+            .ok_or_else(|| {
+                Error::from_io3(
+                    io::Error::from_raw_os_error(libc::EFAULT),
+                    "process_vm_io::CheckpointImage::read",
+                    self.process_id,
+                )
+            })?;
+
+        let offset_in_entry = address - entry.address;
+        let size = cmp::min(buf.len() as u64, entry.size - offset_in_entry) as usize;
+        self.pages
+            .read_exact_at(&mut buf[..size], entry.offset + offset_in_entry)
+            .map_err(|err| Error::from_io3(err, "pread(pages-[id].img)", self.process_id))?;
+
+        self.address = address.checked_add(size as u64);
+        Ok(size)
+    }
+}
+
+impl Read for CheckpointImage {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.read_raw(buf).map_err(io::Error::from)
+    }
+}
+
+impl Seek for CheckpointImage {
+    /// Seek to an address in the virtual memory address space of the
+    /// checkpointed process.
+    ///
+    /// This behaves like seeking a
+    /// [`ProcessVirtualMemoryIO`](crate::ProcessVirtualMemoryIO).
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.address = seek_address(self.address, pos)?;
+        Ok(self.address.unwrap_or(u64::MAX))
+    }
+}
+
+/// Parse the contents of a `pagemap` image file, returning the identifier of
+/// the associated `pages` image file, and the address ranges whose data is
+/// stored in it, sorted by address.
+///
+/// The file holds magic numbers, followed by a `pagemap_head` message, then
+/// by `pagemap_entry` messages. Each message is encoded in the protocol
+/// buffers format, and prefixed by its size.
+fn parse_pagemap(contents: &[u8], page_size: u64) -> Option<(u64, Vec<PagemapEntry>)> {
+    let mut input = contents;
+    let mut magic = take_u32(&mut input)?;
+    if magic == IMG_COMMON_MAGIC {
+        magic = take_u32(&mut input)?;
+    }
+    if magic != PAGEMAP_MAGIC {
+        return None;
+    }
+
+    let head = take_message(&mut input)?;
+    let pages_id = protobuf_varint_field(head, 1)?.unwrap_or(0);
+
+    let mut entries = Vec::new();
+    let mut offset = 0_u64;
+    while !input.is_empty() {
+        let message = take_message(&mut input)?;
+        let address = protobuf_varint_field(message, 1)??;
+        let page_count = protobuf_varint_field(message, 2)??;
+        let in_parent = protobuf_varint_field(message, 3)?.unwrap_or(0) != 0;
+        let flags = protobuf_varint_field(message, 4)?.unwrap_or(if in_parent {
+            PE_PARENT
+        } else {
+            PE_PRESENT
+        });
+
+        if flags & PE_PRESENT != 0 {
+            let size = page_count.checked_mul(page_size)?;
+            address.checked_add(size)?;
+            entries.push(PagemapEntry {
+                address,
+                size,
+                offset,
+            });
+            offset = offset.checked_add(size)?;
+        }
+    }
+
+    entries.sort_unstable_by_key(|entry| entry.address);
+    let overlapping = entries
+        .windows(2)
+        .any(|pair| pair[0].address + pair[0].size > pair[1].address);
+    (!overlapping).then_some((pages_id, entries))
+}
+
+/// Remove a native-endian 32-bit integer from the start of `input`.
+fn take_u32(input: &mut &[u8]) -> Option<u32> {
+    let (bytes, rest) = input.split_first_chunk::<4>()?;
+    *input = rest;
+    Some(u32::from_ne_bytes(*bytes))
+}
+
+/// Remove a message prefixed by its size from the start of `input`.
+fn take_message<'a>(input: &mut &'a [u8]) -> Option<&'a [u8]> {
+    let size = usize::try_from(take_u32(input)?).ok()?;
+    let (message, rest) = input.split_at_checked(size)?;
+    *input = rest;
+    Some(message)
+}
+
+/// Remove a variable-length integer encoded in the protocol buffers format
+/// from the start of `input`.
+fn take_varint(input: &mut &[u8]) -> Option<u64> {
+    let mut result = 0_u64;
+    for shift in (0..64).step_by(7) {
+        let (&byte, rest) = input.split_first()?;
+        *input = rest;
+        result |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(result);
+        }
+    }
+    None
+}
+
+/// Return the last value of the variable-length integer field numbered
+/// `field_number` in a protocol buffers `message`, if any.
+///
+/// Fields of other types are skipped. Returns `None` if `message` is
+/// malformed.
+fn protobuf_varint_field(mut message: &[u8], field_number: u64) -> Option<Option<u64>> {
+    let mut result = None;
+    while !message.is_empty() {
+        let key = take_varint(&mut message)?;
+        match key & 0x7 {
+            0 => {
+                let value = take_varint(&mut message)?;
+                if key >> 3 == field_number {
+                    result = Some(value);
+                }
+            }
+            1 => message = message.get(8..)?,
+            2 => {
+                let size = usize::try_from(take_varint(&mut message)?).ok()?;
+                message = message.get(size..)?;
+            }
+            5 => message = message.get(4..)?,
+            _ => return None,
+        }
+    }
+    Some(result)
+}
@@ -0,0 +1,82 @@
+// Copyright (c) 2020-2024 MicroDoc Software GmbH.
+// See the "LICENSE.txt" file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license. This file may not be copied, modified,
+// or distributed except according to those terms.
+
+/*! Encoding of values stored in the virtual memory of a process. */
+
+/// Byte order of multi-byte values stored in the virtual memory of a process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Endianness {
+    /// Least significant byte first.
+    Little,
+    /// Most significant byte first.
+    Big,
+}
+
+impl Endianness {
+    /// Byte order of the currently running process.
+    pub const fn native() -> Self {
+        if cfg!(target_endian = "big") {
+            Self::Big
+        } else {
+            Self::Little
+        }
+    }
+
+    /// Decode an unsigned integer of up to 8 bytes, zero-extending it to
+    /// 64 bits.
+    pub(crate) fn decode_u64(self, bytes: &[u8]) -> u64 {
+        debug_assert!(bytes.len() <= 8);
+        let accumulate = |value: u64, byte: &u8| (value << 8) | u64::from(*byte);
+        match self {
+            Self::Little => bytes.iter().rev().fold(0, accumulate),
+            Self::Big => bytes.iter().fold(0, accumulate),
+        }
+    }
+}
+
+impl Default for Endianness {
+    fn default() -> Self {
+        Self::native()
+    }
+}
+
+/// Size of pointers stored in the virtual memory of a process.
+///
+/// A 64-bit process inspecting a 32-bit process needs to decode 32-bit pointers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PointerWidth {
+    /// Pointers are 4 bytes long.
+    Bits32,
+    /// Pointers are 8 bytes long.
+    Bits64,
+}
+
+impl PointerWidth {
+    /// Size of pointers of the currently running process.
+    pub const fn native() -> Self {
+        if cfg!(target_pointer_width = "64") {
+            Self::Bits64
+        } else {
+            Self::Bits32
+        }
+    }
+
+    /// Size in bytes of a pointer.
+    pub const fn size(self) -> usize {
+        match self {
+            Self::Bits32 => 4,
+            Self::Bits64 => 8,
+        }
+    }
+}
+
+impl Default for PointerWidth {
+    fn default() -> Self {
+        Self::native()
+    }
+}
@@ -35,6 +35,47 @@ pub enum ErrorKind {
     /// Casting an integer caused data loss.
     #[non_exhaustive]
     IntegerCast(std::num::TryFromIntError),
+
+    /// No data could be transferred at an address, which is likely invalid,
+    /// e.g., not mapped in the target process.
+    ///
+    /// This is reported instead of an `EFAULT` errno code, only if enabled by
+    /// [`Builder::invalid_address_errors`](crate::Builder::invalid_address_errors).
+    #[non_exhaustive]
+    InvalidAddress {
+        /// Address where the data transfer started.
+        address: u64,
+        /// Identifier of the process that was the target of the transfer.
+        process_id: libc::pid_t,
+    },
+
+    /// Data was not written at an address, because it lies in a memory region
+    /// of the target process that is mapped without write permission.
+    ///
+    /// This is reported before trying to write, only if enabled by
+    /// [`ProcessVirtualMemoryIO::set_check_writability`](crate::ProcessVirtualMemoryIO::set_check_writability).
+    #[non_exhaustive]
+    NotWritable {
+        /// Address where writing would have started.
+        address: u64,
+        /// Identifier of the process that was the target of the write.
+        process_id: libc::pid_t,
+    },
+}
+
+/// Broad category of an error, as returned by [`Error::error_category`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorCategory {
+    /// The operating system reported the given errno code.
+    Os(c_int),
+
+    /// An operation was rejected by this crate, e.g., due to invalid input,
+    /// without involving the operating system.
+    Logic,
+
+    /// Casting an integer caused data loss.
+    Cast,
 }
 
 /// Call stack back trace where the `Error` object was created.
@@ -109,6 +150,20 @@ impl fmt::Display for Error {
                 Some(process_id) => write!(f, "{operation}({process_id}): {error}"),
             },
             ErrorKind::IntegerCast(err) => err.fmt(f),
+            ErrorKind::InvalidAddress {
+                address,
+                process_id,
+            } => write!(
+                f,
+                "invalid address {address:#x} in the virtual memory of process {process_id}"
+            ),
+            ErrorKind::NotWritable {
+                address,
+                process_id,
+            } => write!(
+                f,
+                "address {address:#x} is not writable in the virtual memory of process {process_id}"
+            ),
         }
     }
 }
@@ -119,6 +174,8 @@ impl std::error::Error for Error {
             // Errors that are self-descriptive.
             ErrorKind::TooManyVMPages => None,
             ErrorKind::Io { .. } => None,
+            ErrorKind::InvalidAddress { .. } => None,
+            ErrorKind::NotWritable { .. } => None,
 
             // Errors that defer description to the inner error.
             ErrorKind::IntegerCast(err) => Some(err),
@@ -148,7 +205,36 @@ impl From<std::num::TryFromIntError> for Error {
     }
 }
 
+/// Convert an `Error` into an `io::Error` wrapping it.
+///
+/// The kind of the returned `io::Error` is the kind of the underlying
+/// `io::Error`, or the kind of the reported errno code, so that it can be
+/// matched by code handling standard errors. Other errors are of kind
+/// [`Other`](io::ErrorKind::Other). The wrapped `Error` remains available
+/// through [`io::Error::get_ref`] or [`io::Error::into_inner`].
+impl From<Error> for io::Error {
+    fn from(err: Error) -> Self {
+        let kind = match &err.0.kind {
+            ErrorKind::Io { error, .. } => error.kind(),
+            ErrorKind::InvalidAddress { .. } => io::Error::from_raw_os_error(libc::EFAULT).kind(),
+            ErrorKind::NotWritable { .. } => io::ErrorKind::PermissionDenied,
+            ErrorKind::TooManyVMPages | ErrorKind::IntegerCast(_) => io::ErrorKind::Other,
+        };
+        io::Error::new(kind, err)
+    }
+}
+
 impl Error {
+    /// Wrap an `io::Error` into an instance of `Error`.
+    pub(crate) fn from_io2(error: io::Error, operation: &'static str) -> Self {
+        ErrorKind::Io {
+            operation,
+            error: Arc::new(classify_os_error(error)),
+            process_id: None,
+        }
+        .into()
+    }
+
     /// Wrap an `io::Error` into an instance of `Error`, with an associated process ID.
     pub(crate) fn from_io3(
         error: io::Error,
@@ -157,7 +243,7 @@ impl Error {
     ) -> Self {
         ErrorKind::Io {
             operation,
-            error: Arc::new(error),
+            error: Arc::new(classify_os_error(error)),
             process_id: Some(process_id),
         }
         .into()
@@ -172,9 +258,55 @@ impl Error {
     /// reported by the operating system.
     pub fn os_error_code(&self) -> Option<c_int> {
         match &self.0.kind {
-            ErrorKind::TooManyVMPages { .. } => None,
-            ErrorKind::Io { error, .. } => error.raw_os_error(),
+            ErrorKind::TooManyVMPages => None,
+            ErrorKind::Io { error, .. } => raw_os_error(error),
             ErrorKind::IntegerCast { .. } => None,
+            ErrorKind::InvalidAddress { .. } => Some(libc::EFAULT),
+            ErrorKind::NotWritable { .. } => None,
+        }
+    }
+
+    /// Returns the broad category of this error.
+    ///
+    /// Unlike [`os_error_code`](Self::os_error_code), this distinguishes errors
+    /// detected by this crate from integer casting errors.
+    pub fn error_category(&self) -> ErrorCategory {
+        match &self.0.kind {
+            ErrorKind::TooManyVMPages => ErrorCategory::Logic,
+            ErrorKind::Io { error, .. } => {
+                raw_os_error(error).map_or(ErrorCategory::Logic, ErrorCategory::Os)
+            }
+            ErrorKind::IntegerCast { .. } => ErrorCategory::Cast,
+            ErrorKind::InvalidAddress { .. } => ErrorCategory::Os(libc::EFAULT),
+            ErrorKind::NotWritable { .. } => ErrorCategory::Logic,
         }
     }
 }
+
+/// Classify an error reported by the operating system, so that its kind is
+/// consistent across all operations.
+///
+/// Most errno codes are already classified meaningfully, e.g.,
+/// `EACCES` and `EPERM` as [`PermissionDenied`](io::ErrorKind::PermissionDenied),
+/// `EINVAL` as [`InvalidInput`](io::ErrorKind::InvalidInput),
+/// `ENOSYS` as [`Unsupported`](io::ErrorKind::Unsupported).
+/// `EFAULT` is kept as is, since it has no better classification.
+/// `ESRCH` is classified as [`NotFound`](io::ErrorKind::NotFound), wrapping
+/// the original error so that its errno code remains available.
+pub(crate) fn classify_os_error(error: io::Error) -> io::Error {
+    match error.raw_os_error() {
+        Some(libc::ESRCH) => io::Error::new(io::ErrorKind::NotFound, error),
+        _ => error,
+    }
+}
+
+/// Returns the errno code of an error reported by the operating system,
+/// possibly wrapped by [`classify_os_error`].
+fn raw_os_error(error: &io::Error) -> Option<c_int> {
+    error.raw_os_error().or_else(|| {
+        error
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<io::Error>())
+            .and_then(io::Error::raw_os_error)
+    })
+}
@@ -0,0 +1,108 @@
+// Copyright (c) 2020-2024 MicroDoc Software GmbH.
+// See the "LICENSE.txt" file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license. This file may not be copied, modified,
+// or distributed except according to those terms.
+
+/*! Stopping processes for the duration of consistent data transfers. */
+
+use std::io;
+use std::thread;
+use std::time::Duration;
+
+use crate::procfs::{self, Liveness};
+use crate::{Error, Result};
+
+/// Maximum number of times the state of a process is checked while waiting
+/// for it to stop.
+const STOP_POLL_COUNT: u32 = 1000;
+
+/// Delay between two checks of the state of a process while waiting for it
+/// to stop.
+const STOP_POLL_DELAY: Duration = Duration::from_millis(1);
+
+/// Guard keeping a process stopped, as returned by
+/// [`ProcessVirtualMemoryIO::freeze`](crate::ProcessVirtualMemoryIO::freeze).
+///
+/// When dropped, the process is continued by sending it the `SIGCONT` signal,
+/// unless it was already stopped when the guard was created.
+#[derive(Debug)]
+#[must_use = "the process is continued as soon as the guard is dropped"]
+pub struct FreezeGuard {
+    process_id: libc::pid_t,
+
+    /// Whether the process must be continued when this guard is dropped.
+    resume: bool,
+}
+
+impl FreezeGuard {
+    /// Stop the process identified by `process_id`, and wait until it stops.
+    pub(crate) fn new(process_id: libc::pid_t) -> Result<Self> {
+        const OPERATION: &str = "process_vm_io::ProcessVirtualMemoryIO::freeze";
+
+        if procfs::is_current_thread_group(process_id)? {
+            // The calling thread would stop too, and never continue.
+            return Err(Error::from_io3(
+                io::ErrorKind::InvalidInput.into(),
+                OPERATION,
+                process_id,
+            ));
+        }
+
+        if procfs::liveness(process_id)? == Liveness::Stopped {
+            return Ok(Self {
+                process_id,
+                resume: false,
+            });
+        }
+
+        send_signal(process_id, libc::SIGSTOP)?;
+
+        // From here on, dropping the guard continues the process.
+        let guard = Self {
+            process_id,
+            resume: true,
+        };
+
+        for _ in 0..STOP_POLL_COUNT {
+            if procfs::liveness(process_id)? != Liveness::Running {
+                return Ok(guard);
+            }
+            thread::sleep(STOP_POLL_DELAY);
+        }
+
+        // Dropping the guard continues the process, in case it stops later.
+        Err(Error::from_io3(
+            io::ErrorKind::TimedOut.into(),
+            OPERATION,
+            process_id,
+        ))
+    }
+
+    /// Returns `true` if the process was already stopped when this guard was
+    /// created, in which case dropping this guard does not continue it.
+    pub fn was_already_stopped(&self) -> bool {
+        !self.resume
+    }
+}
+
+impl Drop for FreezeGuard {
+    fn drop(&mut self) {
+        if self.resume {
+            // The process might have terminated in the meantime.
+            let _ignored = send_signal(self.process_id, libc::SIGCONT);
+        }
+    }
+}
+
+/// Send the signal `signal` to the process identified by `process_id`.
+fn send_signal(process_id: libc::pid_t, signal: libc::c_int) -> Result<()> {
+    if unsafe { libc::kill(process_id, signal) } == -1 {
+        return Err(Error::from_io3(
+            io::Error::last_os_error(),
+            "kill",
+            process_id,
+        ));
+    }
+    Ok(())
+}
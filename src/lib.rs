@@ -27,21 +27,65 @@
 )]
 #![allow(clippy::upper_case_acronyms)]
 
+mod bounded;
+mod builder;
+mod checkpoint;
+mod encoding;
 mod errors;
+mod freeze;
+mod lines;
+mod pool;
+mod probe;
+mod procfs;
+mod sparse;
+mod strings;
 #[cfg(test)]
 mod tests;
+mod trace;
 
+pub use bounded::BoundedReader;
+pub use builder::Builder;
+pub use checkpoint::CheckpointImage;
+pub use encoding::{Endianness, PointerWidth};
 pub use errors::*;
+pub use freeze::FreezeGuard;
+pub use lines::RemoteLines;
+pub use pool::BufferPool;
+pub use probe::{probe_capabilities, Capabilities};
+pub use procfs::{
+    diff_maps, Liveness, MapDiff, MemoryRegion, MemorySummary, Permissions, ProcStatus, SchedStats,
+    SignalSet, SignalState,
+};
+pub use sparse::SparseImage;
+pub use strings::RemoteString;
+pub use trace::TraceEvent;
 
+use std::borrow::Cow;
+use std::collections::HashSet;
 use std::convert::TryFrom;
-use std::ffi::c_void;
+use std::ffi::{c_void, CStr};
+use std::fs::{File, OpenOptions};
 use std::io::{IoSlice, IoSliceMut, Read, Seek, SeekFrom, Write};
-use std::os::raw::c_ulong;
-use std::{cmp, io, panic, slice};
+use std::mem;
+#[cfg(debug_assertions)]
+use std::mem::MaybeUninit;
+use std::num::{NonZeroU64, NonZeroUsize};
+use std::ops::Range;
+use std::os::fd::{AsFd, AsRawFd, BorrowedFd, FromRawFd, OwnedFd, RawFd};
+use std::os::raw::{c_uint, c_ulong};
+use std::path::{Path, PathBuf};
+#[cfg(feature = "metrics")]
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::{self, AtomicBool, AtomicU8};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use std::{cmp, io, ptr, slice};
 
 use lazy_static::lazy_static;
 use smallvec::SmallVec;
 
+use pool::ScratchBuffer;
+
 lazy_static! {
     /// Size in bytes of the smallest possible virtual memory page.
     ///
@@ -64,6 +108,205 @@ lazy_static! {
         };
 }
 
+/// Return the address range of the stack of the calling thread, if known.
+#[cfg(debug_assertions)]
+fn current_thread_stack() -> Option<Range<u64>> {
+    let mut attributes = MaybeUninit::<libc::pthread_attr_t>::uninit();
+    if unsafe { libc::pthread_getattr_np(libc::pthread_self(), attributes.as_mut_ptr()) } != 0 {
+        return None;
+    }
+
+    let mut stack_address = ptr::null_mut();
+    let mut stack_size = 0;
+    let result = unsafe {
+        libc::pthread_attr_getstack(attributes.as_ptr(), &mut stack_address, &mut stack_size)
+    };
+    unsafe { libc::pthread_attr_destroy(attributes.as_mut_ptr()) };
+
+    let start = stack_address as u64;
+    (result == 0).then(|| start..start.saturating_add(stack_size as u64))
+}
+
+/// Type of the kernel resources compared by `kcmp()`: virtual memories.
+const KCMP_VM: libc::c_int = 1;
+
+/// Determine whether the process identified by `process_id` shares the
+/// virtual memory of the current process, i.e., whether it is the current
+/// process, one of its threads, or a process created by `clone()` with the
+/// `CLONE_VM` flag.
+///
+/// The virtual memories are compared by `kcmp()`. If `kcmp()` is denied
+/// access to the process, then so are data transfers, so the process is
+/// reported as not sharing the virtual memory. Other errors are returned.
+fn shares_current_virtual_memory(process_id: libc::pid_t) -> Result<bool> {
+    if procfs::is_current_thread_group(process_id)? {
+        return Ok(true);
+    }
+
+    let current_process_id = std::process::id() as libc::pid_t;
+    let unused_index: c_ulong = 0;
+    let result = unsafe {
+        libc::syscall(
+            libc::SYS_kcmp,
+            current_process_id,
+            process_id,
+            KCMP_VM,
+            unused_index,
+            unused_index,
+        )
+    };
+    if result != -1 {
+        return Ok(result == 0);
+    }
+
+    let err = io::Error::last_os_error();
+    match err.raw_os_error() {
+        Some(libc::EPERM | libc::EACCES) => Ok(false),
+        _ => Err(Error::from_io3(err, "kcmp", process_id)),
+    }
+}
+
+/// Pathnames of the pseudo-regions that are readable according to their
+/// permissions, but that cannot be read through `process_vm_readv()`.
+const UNREADABLE_PSEUDO_REGIONS: [&str; 3] = ["[vvar]", "[vvar_vclock]", "[vsyscall]"];
+
+/// Return the size in bytes of the smallest possible virtual memory page of
+/// the current system, i.e., the host page size.
+///
+/// Data transfers are split at boundaries of pages of the target process,
+/// whose size is the host page size unless configured otherwise by
+/// [`Builder::target_page_size`]. See
+/// [`ProcessVirtualMemoryIO::target_page_size`].
+pub fn system_page_size() -> Result<NonZeroU64> {
+    match *MIN_SYSTEM_PAGE_SIZE {
+        u64::MAX => Err(Error::from_io2(
+            io::ErrorKind::Unsupported.into(),
+            "sysconf(_SC_PAGE_SIZE)",
+        )),
+        page_size => NonZeroU64::new(page_size).ok_or_else(|| {
+            Error::from_io2(io::ErrorKind::InvalidData.into(), "sysconf(_SC_PAGE_SIZE)")
+        }),
+    }
+}
+
+/// Return the maximum number of `iovec` structures that can be provided to
+/// one system call, as used to limit the size of data transfers.
+///
+/// One data transfer covers at most `system_iov_max()` pages, i.e.,
+/// at most `system_iov_max() * system_page_size()` bytes.
+pub fn system_iov_max() -> NonZeroUsize {
+    NonZeroUsize::new(*SYSTEM_IOV_MAX).unwrap_or(NonZeroUsize::MIN)
+}
+
+/// Compute the address resulting from seeking to `pos` from `address`, as
+/// done by [`Seek::seek`]. An address of `None` is **past** the end of the
+/// address space.
+pub(crate) fn seek_address(address: Option<u64>, pos: SeekFrom) -> io::Result<Option<u64>> {
+    let result = match (address, pos) {
+        (_, SeekFrom::Start(n)) => Some(n),
+
+        (None, SeekFrom::Current(n)) if n >= 0 => None,
+        (_, SeekFrom::End(n)) if n >= 0 => None,
+
+        (Some(address), SeekFrom::Current(n)) if n >= 0 => {
+            let forward = n as u64;
+            address.checked_add(forward)
+        }
+
+        (None, SeekFrom::Current(n)) /* if n < 0 */ => {
+            let backward = n.wrapping_neg() as u64;
+            Some((u64::MAX - backward) + 1)
+        }
+        (_, SeekFrom::End(n)) /* if n < 0 */ => {
+            let backward = n.wrapping_neg() as u64;
+            Some((u64::MAX - backward) + 1)
+        }
+
+        (Some(address), SeekFrom::Current(n)) /* if n < 0 */ => {
+            let backward = n.wrapping_neg() as u64;
+            address.checked_sub(backward).ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput)).map(Some)?
+        }
+    };
+    Ok(result)
+}
+
+/// Convert an address `range` into its start address and its size in bytes.
+///
+/// If `range.start` is greater than `range.end`, then an error of kind
+/// [`InvalidInput`](io::ErrorKind::InvalidInput) is returned.
+pub fn range_to_extent(range: Range<u64>) -> Result<(u64, u64)> {
+    match range.end.checked_sub(range.start) {
+        Some(size) => Ok((range.start, size)),
+        None => Err(Error::from_io2(
+            io::ErrorKind::InvalidInput.into(),
+            "process_vm_io::range_to_extent",
+        )),
+    }
+}
+
+/// Copy `len` bytes from the virtual memory of the process of `src`, at its
+/// current address, to the virtual memory of the process of `dst`, at its
+/// current address, then advance both current addresses by the number of
+/// bytes copied, which is returned.
+///
+/// Data is copied through a local buffer of at most 64 pages of `src`,
+/// issuing as many system calls as needed. Copying stops early at the first
+/// address that cannot be read from `src` or written to `dst`. If no data can
+/// be copied at all, then an error is returned. If the current address of
+/// either object is after the end of the address space, then nothing is
+/// copied.
+///
+/// Copying is not atomic: both processes may run during the copy, so the
+/// data read from `src` might be modified before it is written to `dst`, and
+/// `dst` might observe partially copied data.
+pub fn copy_between(
+    src: &mut ProcessVirtualMemoryIO,
+    dst: &mut ProcessVirtualMemoryIO,
+    len: u64,
+) -> Result<u64> {
+    const MAX_BUFFER_PAGES: usize = 64;
+
+    let (Some(src_address), Some(dst_address)) = (src.address, dst.address) else {
+        return Ok(0);
+    };
+
+    let buffer_pages = cmp::min(src.iov_max.get(), MAX_BUFFER_PAGES) as u64;
+    let buffer_size = cmp::min(len, src.page_size * buffer_pages);
+    let mut buffer = vec![0_u8; usize::try_from(buffer_size)?];
+
+    let mut total = 0;
+    while total < len {
+        let size = cmp::min(len - total, buffer_size) as usize;
+        let (Some(from), Some(to)) = (
+            src_address.checked_add(total),
+            dst_address.checked_add(total),
+        ) else {
+            break; // End of the address space.
+        };
+
+        let read_count = match src.read_at(from, &mut buffer[..size]) {
+            Ok(count) => count,
+            Err(err) if total == 0 => return Err(err),
+            Err(_) => break,
+        };
+
+        let written_count = match dst.write_at(to, &buffer[..read_count]) {
+            Ok(count) => count,
+            Err(err) if total == 0 => return Err(err),
+            Err(_) => break,
+        };
+
+        total += written_count as u64;
+        if written_count < size {
+            break;
+        }
+    }
+
+    src.address = src_address.checked_add(total);
+    dst.address = dst_address.checked_add(total);
+    Ok(total)
+}
+
 /// Align a given number down to a specified alignment boundary.
 const fn align_down(n: u64, alignment: u64) -> u64 {
     // Notice that the calculation below never causes an overflow.
@@ -80,6 +323,151 @@ type ProcessVMReadVProc = unsafe extern "C" fn(
     c_ulong,
 ) -> isize;
 
+/// Prototype of the methods transferring data between local and remote
+/// `iovec`s, e.g.,
+/// [`transfer_io_vector_batches`](ProcessVirtualMemoryIO::transfer_io_vector_batches).
+type TransferIoVectorsProc = fn(
+    &ProcessVirtualMemoryIO,
+    ProcessVMReadVProc,
+    &[libc::iovec],
+    &[libc::iovec],
+) -> (io::Result<usize>, Backend);
+
+lazy_static! {
+    /// The `process_vm_readv()` function of the C library, if it provides it.
+    static ref LIBC_PROCESS_VM_READV: Option<ProcessVMReadVProc> =
+        unsafe { resolve_libc_function(c"process_vm_readv") };
+
+    /// The `process_vm_writev()` function of the C library, if it provides it.
+    static ref LIBC_PROCESS_VM_WRITEV: Option<ProcessVMReadVProc> =
+        unsafe { resolve_libc_function(c"process_vm_writev") };
+}
+
+/// Look up the function named `name` in the C library, at run time.
+///
+/// The function must have the prototype [`ProcessVMReadVProc`].
+unsafe fn resolve_libc_function(name: &CStr) -> Option<ProcessVMReadVProc> {
+    let address = unsafe { libc::dlsym(libc::RTLD_DEFAULT, name.as_ptr()) };
+    (!address.is_null())
+        .then(|| unsafe { mem::transmute::<*mut c_void, ProcessVMReadVProc>(address) })
+}
+
+/// Call `process_vm_readv()`.
+///
+/// The function is looked up in the C library at run time, so that this crate
+/// does not require the C library to provide it. If it does not, e.g., in some
+/// statically linked programs, then the system call is issued directly.
+/// Kernels that do not support the system call make it fail with an `ENOSYS`
+/// errno code.
+pub(crate) unsafe extern "C" fn process_vm_readv(
+    process_id: libc::pid_t,
+    local_iov: *const libc::iovec,
+    local_iov_count: c_ulong,
+    remote_iov: *const libc::iovec,
+    remote_iov_count: c_ulong,
+    flags: c_ulong,
+) -> isize {
+    match *LIBC_PROCESS_VM_READV {
+        Some(function) => unsafe {
+            function(
+                process_id,
+                local_iov,
+                local_iov_count,
+                remote_iov,
+                remote_iov_count,
+                flags,
+            )
+        },
+        None => unsafe {
+            process_vm_syscall(
+                libc::SYS_process_vm_readv,
+                process_id,
+                local_iov,
+                local_iov_count,
+                remote_iov,
+                remote_iov_count,
+                flags,
+            )
+        },
+    }
+}
+
+/// Call `process_vm_writev()`, as done by [`process_vm_readv`].
+pub(crate) unsafe extern "C" fn process_vm_writev(
+    process_id: libc::pid_t,
+    local_iov: *const libc::iovec,
+    local_iov_count: c_ulong,
+    remote_iov: *const libc::iovec,
+    remote_iov_count: c_ulong,
+    flags: c_ulong,
+) -> isize {
+    match *LIBC_PROCESS_VM_WRITEV {
+        Some(function) => unsafe {
+            function(
+                process_id,
+                local_iov,
+                local_iov_count,
+                remote_iov,
+                remote_iov_count,
+                flags,
+            )
+        },
+        None => unsafe {
+            process_vm_syscall(
+                libc::SYS_process_vm_writev,
+                process_id,
+                local_iov,
+                local_iov_count,
+                remote_iov,
+                remote_iov_count,
+                flags,
+            )
+        },
+    }
+}
+
+/// Issue the system call `number`, i.e., `process_vm_readv()` or
+/// `process_vm_writev()`, without the C library.
+unsafe fn process_vm_syscall(
+    number: libc::c_long,
+    process_id: libc::pid_t,
+    local_iov: *const libc::iovec,
+    local_iov_count: c_ulong,
+    remote_iov: *const libc::iovec,
+    remote_iov_count: c_ulong,
+    flags: c_ulong,
+) -> isize {
+    unsafe {
+        libc::syscall(
+            number,
+            process_id,
+            local_iov,
+            local_iov_count,
+            remote_iov,
+            remote_iov_count,
+            flags,
+        ) as isize
+    }
+}
+
+/// Return `true` if `process_vm_io_v` writes to the virtual memory of a
+/// process.
+fn is_write(process_vm_io_v: ProcessVMReadVProc) -> bool {
+    let process_vm_writev: ProcessVMReadVProc = process_vm_writev;
+    ptr::fn_addr_eq(process_vm_io_v, process_vm_writev)
+}
+
+/// Name of the system call performing the transfer of `process_vm_io_v` with
+/// the given `backend`.
+fn system_call_name(process_vm_io_v: ProcessVMReadVProc, backend: Backend) -> &'static str {
+    match (backend, is_write(process_vm_io_v)) {
+        (Backend::Syscall, false) => "process_vm_readv",
+        (Backend::Syscall, true) => "process_vm_writev",
+        (Backend::ProcMem, false) => "pread(/proc/[pid]/mem)",
+        (Backend::ProcMem, true) => "pwrite(/proc/[pid]/mem)",
+    }
+}
+
 /// An address range which is split, at page boundaries, over multiple sections.
 ///
 /// The address range spans as follows:
@@ -97,7 +485,18 @@ struct PageAwareAddressRange {
 impl PageAwareAddressRange {
     /// Convert a plain address range into an address range which is split,
     /// at page boundaries, over multiple sections.
-    fn new(start_address: u64, mut size: u64) -> Self {
+    #[cfg(test)]
+    fn new(start_address: u64, size: u64) -> Self {
+        Self::with_page_size(start_address, size, *MIN_SYSTEM_PAGE_SIZE)
+    }
+
+    /// Convert a plain address range into an address range which is split,
+    /// at boundaries of pages of `min_page_size` bytes, over multiple sections.
+    ///
+    /// `min_page_size` must be a power of two.
+    fn with_page_size(start_address: u64, mut size: u64, min_page_size: u64) -> Self {
+        debug_assert!(min_page_size.is_power_of_two());
+
         if size == 0 {
             return Self {
                 start_address,
@@ -107,7 +506,6 @@ impl PageAwareAddressRange {
             };
         }
 
-        let min_page_size = *MIN_SYSTEM_PAGE_SIZE;
         let distance_to_preceeding_page_boundary =
             start_address - align_down(start_address, min_page_size);
 
@@ -168,9 +566,31 @@ impl PageAwareAddressRange {
     /// be included in the returned vector of `iovec`s. The size of that suffix
     /// (if any) is also returned. Returning a vector of `iovec`s that covers
     /// only a prefix of this address range is not considered a failure.
-    fn into_iov_buffers(mut self) -> Result<(SmallVec<[libc::iovec; 3]>, u64)> {
-        let min_page_size = *MIN_SYSTEM_PAGE_SIZE;
-        let max_iov_count = *SYSTEM_IOV_MAX;
+    ///
+    /// At most `max_iov_count` `iovec`s are returned, one per page of
+    /// `min_page_size` bytes, which must be the page size used to split this
+    /// address range.
+    fn into_iov_buffers(
+        self,
+        min_page_size: u64,
+        max_iov_count: usize,
+    ) -> Result<(SmallVec<[libc::iovec; 3]>, u64)> {
+        self.into_iov_buffers_with(min_page_size, max_iov_count, |count| {
+            let mut result = SmallVec::new();
+            result.try_reserve_exact(count).ok()?;
+            Some(result)
+        })
+    }
+
+    /// Transform this address range into a vector of `iovec`s, as done by
+    /// [`into_iov_buffers`](Self::into_iov_buffers), allocating vectors of
+    /// more than 3 `iovec`s with `allocate`, which returns `None` on failure.
+    fn into_iov_buffers_with(
+        mut self,
+        min_page_size: u64,
+        max_iov_count: usize,
+        mut allocate: impl FnMut(usize) -> Option<SmallVec<[libc::iovec; 3]>>,
+    ) -> Result<(SmallVec<[libc::iovec; 3]>, u64)> {
         let mut size_of_not_covered_suffix = 0;
 
         let mut inner_pages_count = usize::try_from(self.size_of_inner_pages / min_page_size)?;
@@ -181,8 +601,8 @@ impl PageAwareAddressRange {
         let mut count = {
             if total_count > max_iov_count && self.size_in_last_page != 0 {
                 // Skip the last page.
-                self.size_in_last_page = 0;
                 size_of_not_covered_suffix += self.size_in_last_page;
+                self.size_in_last_page = 0;
                 total_count -= 1;
             }
 
@@ -204,17 +624,17 @@ impl PageAwareAddressRange {
                 break SmallVec::with_capacity(count);
             }
 
-            match panic::catch_unwind(|| SmallVec::with_capacity(count)) {
-                Ok(result) => {
+            match allocate(count) {
+                Some(result) => {
                     // Successfully allocated on the heap.
                     break result;
                 }
 
-                Err(_err) => {
+                None => {
                     if self.size_in_last_page != 0 {
                         // Retry without allocating the `iovec` of the last page.
-                        self.size_in_last_page = 0;
                         size_of_not_covered_suffix += self.size_in_last_page;
+                        self.size_in_last_page = 0;
                         count -= 1;
                     } else if inner_pages_count >= 2 {
                         // Retry without allocating half the `iovec`s of the inner pages.
@@ -259,11 +679,134 @@ impl PageAwareAddressRange {
     }
 }
 
+/// Sequence of `iovec`s taken by byte counts, splitting an `iovec` that holds
+/// more bytes than taken.
+struct IoVectorParts<'a> {
+    io_vectors: slice::Iter<'a, libc::iovec>,
+    /// Remainder of the `iovec` split by the previous take, if any.
+    pending: Option<libc::iovec>,
+}
+
+impl<'a> IoVectorParts<'a> {
+    fn new(io_vectors: &'a [libc::iovec]) -> Self {
+        Self {
+            io_vectors: io_vectors.iter(),
+            pending: None,
+        }
+    }
+
+    /// Append to `result` the next `iovec`s, holding at most `size` bytes in
+    /// total, and return the number of bytes they hold.
+    fn take(&mut self, size: usize, result: &mut Vec<libc::iovec>) -> usize {
+        let mut taken = 0;
+        while taken < size {
+            let Some(part) = self
+                .pending
+                .take()
+                .or_else(|| self.io_vectors.next().copied())
+            else {
+                break;
+            };
+
+            let part_size = cmp::min(part.iov_len, size - taken);
+            result.push(libc::iovec {
+                iov_base: part.iov_base,
+                iov_len: part_size,
+            });
+            if part_size < part.iov_len {
+                self.pending = Some(libc::iovec {
+                    iov_base: part.iov_base.wrapping_byte_add(part_size),
+                    iov_len: part.iov_len - part_size,
+                });
+            }
+            taken += part_size;
+        }
+        taken
+    }
+}
+
+/// Layout of the remote `iovec`s that a data transfer would use, as computed
+/// by [`ProcessVirtualMemoryIO::plan_transfer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TransferPlan {
+    /// Address and size of each remote `iovec`, in order.
+    /// An address range spanning few enough pages is described by one
+    /// `iovec`. Otherwise, each `iovec` describes a page (or a part of a
+    /// page).
+    pub segments: Vec<(u64, usize)>,
+
+    /// Size of the suffix of the address range that is not covered by
+    /// `segments`, due to system/memory limits or to the configured
+    /// [maximum bytes per call](ProcessVirtualMemoryIO::set_max_bytes_per_call).
+    /// Transferring data over the address range would at most transfer
+    /// the covered prefix.
+    pub size_of_not_covered_suffix: u64,
+}
+
+/// Outcome of a data transfer, as returned by
+/// [`ProcessVirtualMemoryIO::read_vectored_report`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct TransferReport {
+    /// Number of bytes transferred.
+    pub transferred: usize,
+
+    /// Whether the requested address range was not entirely covered by the
+    /// transfer, due to system/memory limits or to the configured
+    /// [maximum bytes per call](ProcessVirtualMemoryIO::set_max_bytes_per_call).
+    /// A short transfer is then not a sign of an inaccessible address.
+    pub coverage_limited: bool,
+
+    /// Size of the suffix of the requested address range that was not
+    /// covered by the transfer, which is zero unless `coverage_limited`.
+    pub uncovered: u64,
+}
+
+/// Mechanism transferring data from/to the virtual memory of a process, as
+/// reported by [`ProcessVirtualMemoryIO::last_backend`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Backend {
+    /// The `process_vm_readv()` and `process_vm_writev()` system calls.
+    Syscall,
+
+    /// The `pread()` and `pwrite()` system calls on the `/proc/[pid]/mem`
+    /// file of the target process, used when the system does not support
+    /// `process_vm_readv()` and `process_vm_writev()`, or when that file is
+    /// given to [`ProcessVirtualMemoryIO::from_proc_mem_file`].
+    ProcMem,
+}
+
+impl Backend {
+    /// Encode this backend as a non-zero integer.
+    fn encode(self) -> u8 {
+        match self {
+            Self::Syscall => 1,
+            Self::ProcMem => 2,
+        }
+    }
+
+    /// Decode a backend encoded by [`encode`](Self::encode), or zero.
+    fn decode(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Self::Syscall),
+            2 => Some(Self::ProcMem),
+            _ => None,
+        }
+    }
+}
+
 /// Input/Output object transferring data to/from the virtual memory contents
 /// of a particular process.
 ///
 /// For better performance, consider doing buffered I/O based on the standard
 /// [`BufReader`](std::io::BufReader) and [`BufWriter`](std::io::BufWriter).
+///
+/// Generic code taking a [`Read`], [`Write`] or [`Seek`] implementation by
+/// value also accepts `&mut ProcessVirtualMemoryIO`, through the blanket
+/// implementations of the standard library. This lends the object without
+/// moving it, keeping it usable, along with its inherent methods, afterwards.
 #[derive(Debug)]
 #[non_exhaustive]
 pub struct ProcessVirtualMemoryIO {
@@ -272,6 +815,107 @@ pub struct ProcessVirtualMemoryIO {
     /// Current virtual memory address where I/O happens in the target process.
     /// A value of `None` means we are **past** the end of the address space.
     address: Option<u64>,
+
+    /// Byte order of values stored in the virtual memory of the target process.
+    endianness: Endianness,
+
+    /// Size of pointers stored in the virtual memory of the target process.
+    pointer_width: PointerWidth,
+
+    /// Maximum number of pages of the target process accessed by one
+    /// system call, if limited.
+    page_budget: Option<NonZeroUsize>,
+
+    /// Maximum number of bytes transferred by one system call, if limited.
+    max_bytes_per_call: Option<NonZeroU64>,
+
+    /// Size in bytes of the pages of the target process, used to split data
+    /// transfers at page boundaries.
+    page_size: u64,
+
+    /// Maximum number of `iovec`s accepted by one system call, as returned by
+    /// [`system_iov_max()`], resolved once so that data transfers need not
+    /// look it up.
+    iov_max: NonZeroUsize,
+
+    /// Maximum number of unreadable bytes that [`read`](Read::read) skips
+    /// to reach the next readable memory region, if skipping is enabled.
+    max_hole_skip: Option<u64>,
+
+    /// Total number of bytes skipped by [`read`](Read::read).
+    skipped_bytes: u64,
+
+    /// Hook called after each system call transferring data, if any.
+    trace_hook: Option<trace::TraceHook>,
+
+    /// Memory regions of the target process, as last read by
+    /// [`refresh_maps`](Self::refresh_maps), if any.
+    maps_cache: Option<Vec<MemoryRegion>>,
+
+    /// File descriptor referring to the target process, once opened by
+    /// [`process_fd`](Self::process_fd).
+    process_fd: OnceLock<OwnedFd>,
+
+    /// Whether each successful data transfer is followed by checking that the
+    /// process referred to by [`process_fd`](Self::process_fd) did not exit,
+    /// as set by [`from_pidfd`](Self::from_pidfd).
+    verify_process_fd: bool,
+
+    /// Start time of the target process, recorded when creating this instance
+    /// or by the first call to [`revalidate`](Self::revalidate).
+    start_time: OnceLock<u64>,
+
+    /// Minimum interval between automatic calls to
+    /// [`revalidate`](Self::revalidate) before data transfers, if enabled by
+    /// [`set_auto_revalidate`](Self::set_auto_revalidate).
+    auto_revalidate: Option<Duration>,
+
+    /// Time of the last automatic call to [`revalidate`](Self::revalidate),
+    /// if any.
+    last_revalidation: Mutex<Option<Instant>>,
+
+    /// Whether data transfers use the `/proc/[pid]/mem` file of the target
+    /// process, because the system does not support `process_vm_readv()` and
+    /// `process_vm_writev()`.
+    proc_mem_fallback: AtomicBool,
+
+    /// The `/proc/[pid]/mem` file of the target process, once opened by
+    /// [`proc_mem_file`](Self::proc_mem_file).
+    proc_mem: OnceLock<File>,
+
+    /// Backend of the last successful data transfer, encoded by
+    /// [`Backend::encode`], or zero if there was none.
+    last_backend: AtomicU8,
+
+    /// Data held by [`take_snapshot`](Self::take_snapshot), if any.
+    snapshot: Option<SparseImage>,
+
+    /// Pool of scratch buffers, if set by
+    /// [`set_buffer_pool`](Self::set_buffer_pool).
+    buffer_pool: Option<Arc<BufferPool>>,
+
+    /// Whether transferring data from/to the stack of the calling thread is
+    /// expected, as set by [`Builder::allow_self_stack`].
+    allow_self_stack: bool,
+
+    /// Whether a transfer failing at its first byte with an `EFAULT` errno
+    /// code is reported as [`ErrorKind::InvalidAddress`], as set by
+    /// [`Builder::invalid_address_errors`].
+    invalid_address_errors: bool,
+
+    /// Whether writes are checked against the cached memory regions before
+    /// being issued, as set by [`set_check_writability`](Self::set_check_writability).
+    check_writability: bool,
+
+    /// Whether reads that transfer less than requested because of the limits
+    /// of one system call are continued, as set by
+    /// [`set_auto_continue`](Self::set_auto_continue).
+    auto_continue: bool,
+
+    /// Number of system calls transferring data, per power-of-two bucket of
+    /// transferred bytes.
+    #[cfg(feature = "metrics")]
+    transfer_histogram: [AtomicU64; 64],
 }
 
 impl ProcessVirtualMemoryIO {
@@ -304,181 +948,3421 @@ impl ProcessVirtualMemoryIO {
     /// operations involving this instance may fail.
     /// If the process identifier of the dead process is reused by another
     /// process, then I/O may successfully continue with the new process,
-    /// which might not be the desired behavior.
+    /// which might not be the desired behavior. Instances created by
+    /// [`from_pidfd`](Self::from_pidfd) or
+    /// [`from_pid_with_pidfd`](Self::from_pid_with_pidfd) detect that.
     pub unsafe fn new(process_id: u32, initial_address: u64) -> Result<Self> {
-        let process_id = process_id as libc::pid_t;
-        if process_id <= 0 {
-            return Err(Error::from_io3(
-                io::ErrorKind::InvalidInput.into(),
-                "process_vm_io::ProcessVirtualMemoryIO::new",
-                process_id,
-            ));
+        unsafe {
+            Self::builder(process_id)
+                .initial_address(initial_address)
+                .build()
         }
-
-        Self::ensure_process_exists(process_id)?;
-
-        Ok(Self {
-            process_id,
-            address: Some(initial_address),
-        })
     }
 
-    /// Return the process identifier of the target process.
-    pub fn process_id(&self) -> u32 {
-        self.process_id as u32
-    }
+    /// Create a new object to perform input/output of data from/to the virtual
+    /// memory contents of the process identified by `process_id`, starting at
+    /// the first memory region mapped from a file whose path contains
+    /// `module_substr`.
+    ///
+    /// This is useful to inspect the executable or a library of the target
+    /// process, e.g., with a `module_substr` of `"libc.so"`. If no memory
+    /// region matches, then an error of kind
+    /// [`NotFound`](io::ErrorKind::NotFound) is returned.
+    ///
+    /// # Safety
+    ///
+    /// See [`Self::new`].
+    pub unsafe fn new_at_module(process_id: u32, module_substr: &str) -> Result<Self> {
+        let mut result = unsafe { Self::new(process_id, 0) }?;
 
-    /// Ensure that the process, identified by the given process identifier,
-    /// currently exists in the system.
-    fn ensure_process_exists(process_id: libc::pid_t) -> Result<()> {
-        if unsafe { libc::kill(process_id, 0) } != -1 {
-            return Ok(());
-        }
+        let region = result
+            .memory_maps()?
+            .into_iter()
+            .find(|region| {
+                region
+                    .pathname
+                    .as_deref()
+                    .is_some_and(|pathname| pathname.contains(module_substr))
+            })
+            .ok_or_else(|| {
+                Error::from_io3(
+                    io::ErrorKind::NotFound.into(),
+                    "process_vm_io::ProcessVirtualMemoryIO::new_at_module",
+                    result.process_id,
+                )
+            })?;
 
-        let mut err = io::Error::last_os_error();
-        err = match err.raw_os_error() {
-            Some(libc::ESRCH) => io::Error::from(io::ErrorKind::NotFound),
-            Some(libc::EINVAL) => io::Error::from(io::ErrorKind::InvalidInput),
-            Some(libc::EPERM) => io::Error::from(io::ErrorKind::PermissionDenied),
-            _ => err,
-        };
-        Err(Error::from_io3(err, "kill", process_id))
+        result.address = Some(region.start);
+        Ok(result)
     }
 
-    /// Perform vectored (i.e., scatter/gather) I/O on the virtual memory of the
-    /// target process.
-    fn io_vectored(
-        &mut self,
-        process_vm_io_v: ProcessVMReadVProc,
-        local_io_vectors: &[libc::iovec],
-        mut byte_count: u64,
-    ) -> Result<usize> {
-        if byte_count == 0 || self.address.is_none() {
-            return Ok(0);
-        }
-
-        let address = self.address.unwrap();
-
-        // Do not overflow the address space.
-        let max_remaining_bytes = (u64::MAX - address).saturating_add(1);
-        byte_count = cmp::min(byte_count, max_remaining_bytes);
-
-        let (remote_io_vectors, _size_of_not_covered_suffix) =
-            PageAwareAddressRange::new(address, byte_count).into_iov_buffers()?;
+    /// Create a new object to perform input/output of data from/to the virtual
+    /// memory contents of the process referred to by the file descriptor `fd`,
+    /// i.e., a `pidfd`, e.g., as returned by `pidfd_open()` or by
+    /// `clone()` with the `CLONE_PIDFD` flag.
+    ///
+    /// The `pidfd` keeps referring to the same process, even after that
+    /// process exits and its process identifier is reused by another
+    /// process. Data transfers still identify the target process by its
+    /// process identifier, so each successful data transfer is followed by
+    /// checking, through a duplicate of `fd`, that the target process did not
+    /// exit. If it did, the transferred data might come from another process,
+    /// and an error with the code `ESRCH` is returned instead. This costs one
+    /// more system call per data transfer.
+    ///
+    /// The duplicate of `fd` is returned by [`process_fd`](Self::process_fd),
+    /// and is closed when this instance is dropped.
+    ///
+    /// If `fd` does not refer to a process, then an error of kind
+    /// [`InvalidInput`](io::ErrorKind::InvalidInput) is returned. If the
+    /// process exited, then an error with the code `ESRCH` is returned.
+    ///
+    /// # Safety
+    ///
+    /// See [`Self::new`].
+    pub unsafe fn from_pidfd(fd: BorrowedFd<'_>, initial_address: u64) -> Result<Self> {
+        let fd = fd
+            .try_clone_to_owned()
+            .map_err(|err| Error::from_io2(err, "fcntl(F_DUPFD_CLOEXEC)"))?;
 
-        let transferred_bytes_count = unsafe {
-            process_vm_io_v(
-                self.process_id,
-                local_io_vectors.as_ptr(),
-                local_io_vectors.len() as c_ulong,
-                remote_io_vectors.as_ptr(),
-                remote_io_vectors.len() as c_ulong,
-                0,
-            )
-        };
+        unsafe { Self::from_owned_pidfd(fd, initial_address) }
+    }
 
-        if transferred_bytes_count == -1 {
+    /// Create a new object to perform input/output of data from/to the virtual
+    /// memory contents of the process identified by `process_id`, referring
+    /// to that process by a `pidfd` opened by `pidfd_open()`, as done by
+    /// [`from_pidfd`](Self::from_pidfd).
+    ///
+    /// This protects data transfers from the reuse of `process_id` after the
+    /// `pidfd` is opened, not before. Callers that create the target process
+    /// should rather obtain a `pidfd` when creating it.
+    ///
+    /// # Safety
+    ///
+    /// See [`Self::new`].
+    pub unsafe fn from_pid_with_pidfd(process_id: u32, initial_address: u64) -> Result<Self> {
+        let pid = process_id as libc::pid_t;
+        if pid <= 0 {
             return Err(Error::from_io3(
-                io::Error::last_os_error(),
-                "process_vm_readv/process_vm_writev",
-                self.process_id,
+                io::ErrorKind::InvalidInput.into(),
+                "process_vm_io::ProcessVirtualMemoryIO::from_pid_with_pidfd",
+                pid,
             ));
         }
 
-        self.address = if (transferred_bytes_count as u64) < max_remaining_bytes {
-            Some(address + (transferred_bytes_count as u64))
-        } else {
-            None // End of file (actually, address space).
-        };
-
-        Ok(transferred_bytes_count as usize)
+        let fd = unsafe { Self::new_unchecked(process_id, initial_address) }.open_process_fd()?;
+        unsafe { Self::from_owned_pidfd(fd, initial_address) }
     }
-}
 
-impl Seek for ProcessVirtualMemoryIO {
-    /// Seek to an address in the virtual memory address space of the associated
-    /// process.
+    /// Create a new object to perform input/output of data from/to the virtual
+    /// memory contents of the process identified by `process_id`, through
+    /// `file`, the already open `/proc/[pid]/mem` file of that process, which
+    /// is owned by the returned object.
     ///
-    /// If the seek operation completed successfully, this method returns
-    /// the new position from the start of the stream.
+    /// This supports opening the file elsewhere, e.g., in a privileged
+    /// process that passes it over a Unix socket. Data transfers use
+    /// [`Backend::ProcMem`], i.e., `pread()` and `pwrite()` on `file`, which
+    /// is never reopened. Writing requires `file` to be open for writing.
+    /// [`into_proc_mem_file`](Self::into_proc_mem_file) hands `file` back.
     ///
-    /// The target address need not be readable/writable or even mapped.
-    /// Seeking beyond the address space size puts the cursor after the end
-    /// of the address space.
-    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
-        self.address = match (self.address, pos) {
-            (_, SeekFrom::Start(n)) => Some(n),
+    /// Other operations, e.g., [`memory_maps`](Self::memory_maps), still
+    /// identify the target process by `process_id`. Unlike [`new`](Self::new),
+    /// this does not check that the process exists, as it might not be
+    /// visible to the current process; the first data transfer reports errors
+    /// instead. If `process_id` is not in the range `1..=i32::MAX`, then an
+    /// error of kind [`InvalidInput`](io::ErrorKind::InvalidInput) is
+    /// returned.
+    ///
+    /// # Safety
+    ///
+    /// See [`Self::new`]. The caller must also guarantee that `file` is the
+    /// `/proc/[pid]/mem` file of the process identified by `process_id`.
+    pub unsafe fn from_proc_mem_file(
+        file: File,
+        process_id: u32,
+        initial_address: u64,
+    ) -> Result<Self> {
+        let pid = process_id as libc::pid_t;
+        if pid <= 0 {
+            return Err(Error::from_io3(
+                io::ErrorKind::InvalidInput.into(),
+                "process_vm_io::ProcessVirtualMemoryIO::from_proc_mem_file",
+                pid,
+            ));
+        }
 
-            (None, SeekFrom::Current(n)) if n >= 0 => None,
-            (_, SeekFrom::End(n)) if n >= 0 => None,
+        let mut result = unsafe { Self::new_unchecked(process_id, initial_address) };
+        result.proc_mem = OnceLock::from(file);
+        *result.proc_mem_fallback.get_mut() = true;
+        Ok(result)
+    }
 
-            (Some(address), SeekFrom::Current(n)) if n >= 0 => {
-                let forward = n as u64;
-                address.checked_add(forward)
-            }
+    /// Create a new object to perform input/output of data from/to the virtual
+    /// memory contents of the process referred to by the `pidfd` `fd`, which
+    /// is owned by the returned object.
+    unsafe fn from_owned_pidfd(fd: OwnedFd, initial_address: u64) -> Result<Self> {
+        const OPERATION: &str = "process_vm_io::ProcessVirtualMemoryIO::from_pidfd";
 
-            (None, SeekFrom::Current(n)) /* if n < 0 */ => {
-                let backward = n.wrapping_neg() as u64;
-                Some((u64::MAX - backward) + 1)
+        let process_id = match procfs::pidfd_process_id(fd.as_raw_fd())? {
+            None => {
+                return Err(Error::from_io2(
+                    io::ErrorKind::InvalidInput.into(),
+                    OPERATION,
+                ))
             }
-            (_, SeekFrom::End(n)) /* if n < 0 */ => {
-                let backward = n.wrapping_neg() as u64;
-                Some((u64::MAX - backward) + 1)
+            Some(process_id) if process_id <= 0 => {
+                return Err(Error::from_io2(
+                    io::Error::from_raw_os_error(libc::ESRCH),
+                    OPERATION,
+                ))
             }
+            Some(process_id) => process_id,
+        };
 
-            (Some(address), SeekFrom::Current(n)) /* if n < 0 */ => {
-                let backward = n.wrapping_neg() as u64;
-                address.checked_sub(backward).ok_or_else(|| io::Error::from(io::ErrorKind::InvalidInput)).map(Some)?
+        let mut result = unsafe { Self::new_unchecked(process_id as u32, initial_address) };
+        result.process_fd = OnceLock::from(fd);
+        result.verify_process_fd = true;
+        result
+            .ensure_process_fd_alive()
+            .map_err(|err| Error::from_io3(err, "poll(pidfd)", process_id))?;
+        Ok(result)
+    }
+
+    /// Create a new object to perform input/output of data from/to the virtual
+    /// memory contents of the process identified by `process_id`, without
+    /// checking that the process exists.
+    ///
+    /// This saves a system call compared to [`Self::new`], e.g., when the
+    /// target process was just created by the caller. Errors are reported by
+    /// the first data transfer instead.
+    ///
+    /// # Safety
+    ///
+    /// The caller must guarantee that `process_id` is a valid process
+    /// identifier, i.e., that it is in the range `1..=i32::MAX`.
+    ///
+    /// Writing to the virtual memory of a process is a potentially unsafe
+    /// operation because it may introduce memory unsafety in that process,
+    /// and may lead to unexpected states in that process. This is even more
+    /// dangerous when the target process is the currently running process.
+    pub unsafe fn new_unchecked(process_id: u32, initial_address: u64) -> Self {
+        let process_id = process_id as libc::pid_t;
+        debug_assert!(process_id > 0);
+
+        Self {
+            process_id,
+            address: Some(initial_address),
+            endianness: Endianness::native(),
+            pointer_width: PointerWidth::native(),
+            page_budget: None,
+            max_bytes_per_call: None,
+            page_size: *MIN_SYSTEM_PAGE_SIZE,
+            iov_max: system_iov_max(),
+            max_hole_skip: None,
+            skipped_bytes: 0,
+            trace_hook: None,
+            maps_cache: None,
+            process_fd: OnceLock::new(),
+            verify_process_fd: false,
+            start_time: OnceLock::new(),
+            auto_revalidate: None,
+            last_revalidation: Mutex::new(None),
+            proc_mem_fallback: AtomicBool::new(false),
+            proc_mem: OnceLock::new(),
+            last_backend: AtomicU8::new(0),
+            snapshot: None,
+            buffer_pool: None,
+            allow_self_stack: false,
+            invalid_address_errors: false,
+            check_writability: false,
+            auto_continue: false,
+            #[cfg(feature = "metrics")]
+            transfer_histogram: [const { AtomicU64::new(0) }; 64],
+        }
+    }
+
+    /// Return a builder of an object performing input/output of data from/to
+    /// the virtual memory contents of the process identified by `process_id`.
+    ///
+    /// The builder allows configuring options that [`Self::new`] does not
+    /// provide.
+    pub fn builder(process_id: u32) -> Builder {
+        Builder::new(process_id)
+    }
+
+    /// Return the process identifier of the target process.
+    pub fn process_id(&self) -> u32 {
+        self.process_id as u32
+    }
+
+    /// Set the byte order of values stored in the virtual memory of the target
+    /// process. This defaults to the byte order of the current process.
+    ///
+    /// See also [`Builder::endianness`].
+    pub fn set_endianness(&mut self, endianness: Endianness) {
+        self.endianness = endianness;
+    }
+
+    /// Set the size of pointers stored in the virtual memory of the target
+    /// process. This defaults to the size of pointers of the current process.
+    ///
+    /// All methods reading pointers from the target process honor this setting.
+    /// See also [`Builder::pointer_width`].
+    pub fn set_pointer_width(&mut self, pointer_width: PointerWidth) {
+        self.pointer_width = pointer_width;
+    }
+
+    /// Limit the number of pages of the target process accessed by one system
+    /// call to `pages`.
+    ///
+    /// This bounds the work done by each call to [`read`](Read::read),
+    /// [`write`](Write::write) and their vectored variants, which then transfer
+    /// at most the data stored in `pages` pages, possibly less than requested.
+    /// The returned count of transferred bytes tells where to resume.
+    pub fn set_page_budget(&mut self, pages: NonZeroUsize) {
+        self.page_budget = Some(pages);
+    }
+
+    /// Limit the number of bytes transferred by one system call to `bytes`,
+    /// e.g., to bound the latency of each system call.
+    ///
+    /// Like [`set_page_budget`](Self::set_page_budget), this bounds the work
+    /// done by each call to [`read`](Read::read), [`write`](Write::write) and
+    /// their vectored variants, while methods that issue as many system calls
+    /// as needed, such as [`read_large`](Self::read_large), transfer data in
+    /// chunks of at most `bytes` bytes. When both limits are set, the tightest
+    /// one applies.
+    pub fn set_max_bytes_per_call(&mut self, bytes: NonZeroU64) {
+        self.max_bytes_per_call = Some(bytes);
+    }
+
+    /// Check, if `check` is `true`, that the memory region containing the
+    /// address where a write starts is writable, before writing.
+    ///
+    /// Writing to a memory region mapped without write permission otherwise
+    /// fails with an `EFAULT` errno code, which does not tell why. When
+    /// checking is enabled, such a write instead fails with an error of kind
+    /// [`ErrorKind::NotWritable`], without issuing a system call.
+    ///
+    /// The check is done against the cached memory regions, as maintained by
+    /// [`region_containing`](Self::region_containing), so it remains cheap, but
+    /// becomes stale when the target process changes its mappings. Call
+    /// [`refresh_maps`](Self::refresh_maps) to update them. Addresses that are
+    /// not in any cached memory region are not rejected, and writing there
+    /// reports whatever error the system reports. By default, writes are not
+    /// checked.
+    pub fn set_check_writability(&mut self, check: bool) {
+        self.check_writability = check;
+    }
+
+    /// Make [`read`](Read::read), [`read_raw`](Self::read_raw) and
+    /// [`read_at`](Self::read_at) continue reading, if `auto_continue` is
+    /// `true`, when one system call transfers less than requested only
+    /// because of the limits of one system call, e.g., on the number of pages
+    /// it accesses.
+    ///
+    /// Each of these methods then issues as many system calls as needed,
+    /// until the buffer is full, or until an address cannot be read, in which
+    /// case the data read before it is returned, and the next read reports
+    /// the error. By default, each of these methods issues one system call.
+    pub fn set_auto_continue(&mut self, auto_continue: bool) {
+        self.auto_continue = auto_continue;
+    }
+
+    /// Make [`read`](Read::read) skip holes, i.e., unreadable address ranges,
+    /// of up to `max_skip` bytes, or not skip holes if `max_skip` is `None`.
+    /// By default, holes are not skipped.
+    ///
+    /// When reading at the current address fails because it is not readable,
+    /// the current address is advanced to the start of the next readable
+    /// memory region, if that is at most `max_skip` bytes away, and reading
+    /// continues from there. This makes streaming over sparse memory simpler.
+    /// Memory regions are looked up in the cache maintained by
+    /// [`region_containing`](Self::region_containing), which is refreshed
+    /// when it tells no region to skip to.
+    /// The number of skipped bytes is reported by
+    /// [`skipped_bytes`](Self::skipped_bytes).
+    pub fn set_auto_skip_holes(&mut self, max_skip: Option<u64>) {
+        self.max_hole_skip = max_skip;
+    }
+
+    /// Return the total number of bytes skipped by [`read`](Read::read) when
+    /// skipping holes, as enabled by
+    /// [`set_auto_skip_holes`](Self::set_auto_skip_holes).
+    pub fn skipped_bytes(&self) -> u64 {
+        self.skipped_bytes
+    }
+
+    /// Draw the scratch buffers needed by methods such as
+    /// [`read_cstr`](Self::read_cstr) and [`dump_region`](Self::dump_region)
+    /// from `pool`, instead of allocating them on each call, or allocate them
+    /// again if `pool` is `None`. By default, there is no pool.
+    ///
+    /// See [`BufferPool`], and also [`Builder::buffer_pool`].
+    pub fn set_buffer_pool(&mut self, pool: Option<Arc<BufferPool>>) {
+        self.buffer_pool = pool;
+    }
+
+    /// Call `hook` after each data transfer from/to the virtual memory of the
+    /// target process, with a description of that transfer, including its
+    /// outcome and duration.
+    ///
+    /// A transfer is usually one system call, but see [`TraceEvent`] for
+    /// transfers split into batches, and for how the requested number of
+    /// bytes is limited.
+    ///
+    /// This allows logging or profiling data transfers, e.g., through the
+    /// `log` or `tracing` crates, without this crate depending on them.
+    pub fn set_trace_hook(&mut self, hook: Box<dyn FnMut(TraceEvent) + Send>) {
+        self.trace_hook = Some(trace::TraceHook::new(hook));
+    }
+
+    /// Return the size in bytes of the pages of the target process, as used to
+    /// split data transfers at page boundaries.
+    ///
+    /// This is the page size of the current system, i.e.,
+    /// [`system_page_size()`], unless configured otherwise by
+    /// [`Builder::target_page_size`].
+    pub fn target_page_size(&self) -> u64 {
+        self.page_size
+    }
+
+    /// Return the distribution of the numbers of bytes transferred by the
+    /// successful system calls transferring data so far.
+    ///
+    /// Bucket `i` counts the system calls that transferred from `2^i` to
+    /// `2^(i+1) - 1` bytes, except bucket `0`, which also counts the system
+    /// calls that transferred no data. This tells whether data transfers are
+    /// as large as expected, e.g., when tuning
+    /// [`set_page_budget`](Self::set_page_budget) or buffer sizes.
+    #[cfg(feature = "metrics")]
+    pub fn transfer_histogram(&self) -> [u64; 64] {
+        self.transfer_histogram
+            .each_ref()
+            .map(|count| count.load(atomic::Ordering::Relaxed))
+    }
+
+    /// Return the backend that data transfers use, e.g., to log it.
+    ///
+    /// This is [`Backend::Syscall`], unless a data transfer found that the
+    /// system does not support `process_vm_readv()` and `process_vm_writev()`,
+    /// e.g., because a `seccomp` filter of a container rejects them, in which
+    /// case this is [`Backend::ProcMem`].
+    pub fn backend(&self) -> Backend {
+        if self.proc_mem_fallback.load(atomic::Ordering::Relaxed) {
+            Backend::ProcMem
+        } else {
+            Backend::Syscall
+        }
+    }
+
+    /// Consume this object, and return the `/proc/[pid]/mem` file of the
+    /// target process, if data transfers used it.
+    ///
+    /// This is the file given to
+    /// [`from_proc_mem_file`](Self::from_proc_mem_file), or the file opened
+    /// when falling back to [`Backend::ProcMem`]. If data transfers never
+    /// used that backend, then `None` is returned.
+    pub fn into_proc_mem_file(self) -> Option<File> {
+        self.proc_mem.into_inner()
+    }
+
+    /// Return the backend that performed the last successful data transfer,
+    /// or `None` if no data transfer succeeded yet.
+    ///
+    /// This reflects the last success, not the last attempt: a failed data
+    /// transfer does not change the returned value. This tells, e.g., whether
+    /// a slower fallback backend serves data transfers.
+    pub fn last_backend(&self) -> Option<Backend> {
+        Backend::decode(self.last_backend.load(atomic::Ordering::Relaxed))
+    }
+
+    /// Record the backend that performed a successful data transfer.
+    fn set_last_backend(&self, backend: Backend) {
+        self.last_backend
+            .store(backend.encode(), atomic::Ordering::Relaxed);
+    }
+
+    /// Maximum number of remote `iovec`s to provide to one system call.
+    fn max_iov_count(&self) -> usize {
+        match self.page_budget {
+            None => self.iov_max.get(),
+            Some(pages) => cmp::min(self.iov_max.get(), pages.get()),
+        }
+    }
+
+    /// Return the size of the largest buffer that one call to
+    /// [`read`](Read::read) can fill, i.e., the maximum number of pages
+    /// accessed by one system call, multiplied by the page size, or the
+    /// maximum number of bytes transferred by one system call, if smaller.
+    ///
+    /// This is a good capacity for a [`BufReader`](std::io::BufReader) or a
+    /// [`BufWriter`](std::io::BufWriter) wrapping this object, as it minimizes
+    /// the number of system calls.
+    pub fn recommended_buf_size(&self) -> Result<usize> {
+        let page_size = usize::try_from(self.page_size)?;
+        let size = page_size.checked_mul(self.max_iov_count()).ok_or_else(|| {
+            Error::from_io3(
+                io::ErrorKind::InvalidData.into(),
+                "process_vm_io::ProcessVirtualMemoryIO::recommended_buf_size",
+                self.process_id,
+            )
+        })?;
+
+        Ok(match self.max_bytes_per_call {
+            None => size,
+            Some(max_bytes) => {
+                cmp::min(size, usize::try_from(max_bytes.get()).unwrap_or(usize::MAX))
+            }
+        })
+    }
+
+    /// Determine whether the target process is alive, and whether its virtual
+    /// memory can still be accessed.
+    ///
+    /// Unlike the check performed by [`new`](Self::new), this distinguishes a
+    /// zombie process, which still accepts signals but whose virtual memory is
+    /// gone, from a process that is genuinely running.
+    pub fn is_alive(&self) -> Result<Liveness> {
+        procfs::liveness(self.process_id)
+    }
+
+    /// Check that the process identifier of the target process still
+    /// identifies the same process, i.e., that it was not reused by another
+    /// process after the target process exited.
+    ///
+    /// The start time of the target process is recorded when this instance is
+    /// created by [`new`](Self::new) or by a [`Builder`], and is compared with
+    /// the start time of the process currently identified by the same process
+    /// identifier. If they differ, or if no process is identified by it, then
+    /// an error with the code `ESRCH` is returned. Instances created without
+    /// checking that the target process exists, e.g., by
+    /// [`new_unchecked`](Self::new_unchecked), record the start time on the
+    /// first call to this method.
+    ///
+    /// This reads `/proc/[pid]/stat`, which is cheaper than holding a `pidfd`,
+    /// as done by [`from_pidfd`](Self::from_pidfd), but can miss a reuse of
+    /// the process identifier within the same clock tick as the start of the
+    /// target process.
+    pub fn revalidate(&self) -> Result<()> {
+        const OPERATION: &str = "process_vm_io::ProcessVirtualMemoryIO::revalidate";
+
+        let start_time = match procfs::start_time(self.process_id) {
+            Ok(start_time) => start_time,
+            Err(err) if err.os_error_code() == Some(libc::ENOENT) => {
+                return Err(Error::from_io3(
+                    io::Error::from_raw_os_error(libc::ESRCH),
+                    OPERATION,
+                    self.process_id,
+                ))
             }
+            Err(err) => return Err(err),
+        };
+
+        if *self.start_time.get_or_init(|| start_time) != start_time {
+            return Err(Error::from_io3(
+                io::Error::from_raw_os_error(libc::ESRCH),
+                OPERATION,
+                self.process_id,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Call [`revalidate`](Self::revalidate) before data transfers, at most
+    /// once per `interval`, if `interval` is not `None`, and fail these data
+    /// transfers if that fails. By default, this is not done.
+    ///
+    /// This bounds the time during which data transfers might silently
+    /// access another process than the target process after the target
+    /// process exits, at the cost of reading `/proc/[pid]/stat` once per
+    /// `interval`. An `interval` of zero checks before each data transfer.
+    pub fn set_auto_revalidate(&mut self, interval: Option<Duration>) {
+        self.auto_revalidate = interval;
+    }
+
+    /// Call [`revalidate`](Self::revalidate) if enabled by
+    /// [`set_auto_revalidate`](Self::set_auto_revalidate), and if the last
+    /// such call is older than the configured interval.
+    fn auto_revalidate(&self) -> Result<()> {
+        let Some(interval) = self.auto_revalidate else {
+            return Ok(());
+        };
+
+        let now = Instant::now();
+        let mut last_revalidation = self
+            .last_revalidation
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if last_revalidation.is_some_and(|last| now.duration_since(last) < interval) {
+            return Ok(());
+        }
+
+        self.revalidate()?;
+        *last_revalidation = Some(now);
+        Ok(())
+    }
+
+    /// Return the status of the target process, as described by
+    /// `/proc/[pid]/status`, including its name, state, credentials, memory
+    /// usage, and signal-related state.
+    ///
+    /// This reads everything at once, which is cheaper than separate queries
+    /// when several of those fields are needed.
+    pub fn status(&self) -> Result<ProcStatus> {
+        procfs::status(self.process_id)
+    }
+
+    /// Return the signals that are pending, blocked, ignored or caught by the
+    /// target process.
+    ///
+    /// This helps deciding how the target process would react to a signal,
+    /// e.g., before [freezing](Self::freeze) it.
+    pub fn signal_state(&self) -> Result<SignalState> {
+        procfs::signal_state(self.process_id)
+    }
+
+    /// Return the name of the kernel function in which the target process is
+    /// sleeping, or `None` if it is not sleeping.
+    ///
+    /// The system might hide the actual function name, e.g., when this process
+    /// lacks privileges, in which case `None` is returned as well.
+    pub fn wait_channel(&self) -> Result<Option<String>> {
+        procfs::wait_channel(self.process_id)
+    }
+
+    /// Return the scheduling statistics of the target process.
+    ///
+    /// Kernels built without scheduler statistics cause an error of kind
+    /// [`NotFound`](io::ErrorKind::NotFound) to be returned.
+    pub fn sched_stats(&self) -> Result<SchedStats> {
+        procfs::sched_stats(self.process_id)
+    }
+
+    /// Stop the target process, by sending it the `SIGSTOP` signal, until the
+    /// returned guard is dropped.
+    ///
+    /// Transferring data while the target process is stopped yields a
+    /// consistent view of its virtual memory, e.g., when reading multiple
+    /// memory regions. The guard continues the target process, by sending it
+    /// the `SIGCONT` signal, when dropped. If the target process is already
+    /// stopped, then it is left stopped when the guard is dropped.
+    ///
+    /// This method waits, for a bounded time, until the target process stops.
+    /// If it does not stop in time, then it is continued, and an error of
+    /// kind [`TimedOut`](io::ErrorKind::TimedOut) is returned.
+    ///
+    /// If the target process is the current process, or one of its threads,
+    /// then an error of kind [`InvalidInput`](io::ErrorKind::InvalidInput) is
+    /// returned, as stopping it would also stop the calling thread, forever.
+    pub fn freeze(&self) -> Result<FreezeGuard> {
+        FreezeGuard::new(self.process_id)
+    }
+
+    /// Return the file descriptors opened by the target process, sorted by
+    /// number, along with the files they refer to.
+    ///
+    /// File descriptors that do not refer to a file in the file system refer
+    /// to pseudo-paths such as `socket:[1234]` or `anon_inode:[eventfd]`.
+    /// This requires the same access rights as reading the virtual memory of
+    /// the target process.
+    pub fn open_fds(&self) -> Result<Vec<(u32, PathBuf)>> {
+        procfs::open_fds(self.process_id)
+    }
+
+    /// Return the current address, or `None` if the cursor is after the end of
+    /// the address space.
+    ///
+    /// The position returned by [`Seek::seek`] and [`try_seek`](Self::try_seek)
+    /// is `u64::MAX` in both cases, as the end of the address space, i.e.,
+    /// `2^64`, does not fit in a `u64`. This method tells them apart.
+    /// Otherwise, it returns the same position as [`Seek::stream_position`],
+    /// without going through the [`Seek`] trait, nor failing.
+    pub fn address(&self) -> Option<u64> {
+        self.address
+    }
+
+    /// Set the current address to `address`.
+    ///
+    /// This is equivalent to seeking to [`SeekFrom::Start`], without going
+    /// through the [`Seek`] trait and its `u64` positions.
+    pub fn set_address(&mut self, address: u64) {
+        self.address = Some(address);
+    }
+
+    /// Seek to an address in the virtual memory address space of the associated
+    /// process, rejecting positions outside of the address space.
+    ///
+    /// Unlike [`Seek::seek`], which saturates when seeking forward beyond the
+    /// end of the address space, this method fails with an error of kind
+    /// [`InvalidInput`](io::ErrorKind::InvalidInput) when the target position
+    /// is before the start or after the end of the address space.
+    /// The cursor is left unchanged on failure.
+    ///
+    /// If the seek operation completed successfully, this method returns
+    /// the new position from the start of the stream, as [`Seek::seek`] does.
+    pub fn try_seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        // Positions are computed in a wider type, where the end of the address
+        // space is `1 << 64`.
+        const END_OF_ADDRESS_SPACE: i128 = 1 << 64;
+
+        let current = self.address.map_or(END_OF_ADDRESS_SPACE, i128::from);
+
+        let target = match pos {
+            SeekFrom::Start(n) => i128::from(n),
+            SeekFrom::Current(n) => current + i128::from(n),
+            SeekFrom::End(n) => END_OF_ADDRESS_SPACE + i128::from(n),
         };
 
+        if !(0..=END_OF_ADDRESS_SPACE).contains(&target) {
+            return Err(Error::from_io3(
+                io::ErrorKind::InvalidInput.into(),
+                "process_vm_io::ProcessVirtualMemoryIO::try_seek",
+                self.process_id,
+            ));
+        }
+
+        self.address = u64::try_from(target).ok();
         Ok(self.address.unwrap_or(u64::MAX))
     }
-}
 
-impl Read for ProcessVirtualMemoryIO {
-    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+    /// Ensure that the process, identified by the given process identifier,
+    /// currently exists in the system.
+    fn ensure_process_exists(process_id: libc::pid_t) -> Result<()> {
+        if unsafe { libc::kill(process_id, 0) } != -1 {
+            return Ok(());
+        }
+
+        Err(Error::from_io3(
+            io::Error::last_os_error(),
+            "kill",
+            process_id,
+        ))
+    }
+
+    /// Read data from the virtual memory of the target process at the current
+    /// address into `buf`, then advance the current address by the number of
+    /// bytes read.
+    ///
+    /// This behaves like [`Read::read`], but returns an [`Error`] instead of an
+    /// [`io::Error`], which avoids extracting the [`Error`] out of the
+    /// [`io::Error`] to get its details.
+    pub fn read_raw(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match self.read_once_or_continued(buf) {
+            Err(err) if err.os_error_code() == Some(libc::EFAULT) && self.skip_hole()? => {
+                self.read_once_or_continued(buf)
+            }
+            result => result,
+        }
+    }
+
+    /// Read data from the virtual memory of the target process at the current
+    /// address into `buf`, with one system call, or as many as needed if
+    /// enabled by [`set_auto_continue`](Self::set_auto_continue), then advance
+    /// the current address by the number of bytes read.
+    fn read_once_or_continued(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if !self.auto_continue {
+            let local_io_vector = libc::iovec {
+                iov_base: buf.as_mut_ptr() as *mut c_void,
+                iov_len: buf.len(),
+            };
+            return self.io_vectored(process_vm_readv, &[local_io_vector], buf.len() as u64);
+        }
+
+        let address = match self.address {
+            None => return Ok(0),
+            Some(address) => address,
+        };
+
+        let count = self
+            .read_continued_at(address, buf)
+            .map_err(|err| self.invalid_address_error(err, address))?;
+
+        // `None` means the end of file (actually, address space).
+        self.address = address.checked_add(count as u64);
+        Ok(count)
+    }
+
+    /// Read data from the virtual memory of the target process at `address`
+    /// into `buf`, issuing system calls until `buf` is full, or until a system
+    /// call transfers less than requested for another reason than its limits.
+    ///
+    /// If a system call fails after some data was read, then the count of
+    /// bytes read is returned, and the error is left to the next read.
+    /// The current address is neither used nor modified.
+    fn read_continued_at(&self, address: u64, buf: &mut [u8]) -> Result<usize> {
+        let mut total = 0;
+        while total < buf.len() {
+            let Some(chunk_address) = address.checked_add(total as u64) else {
+                break; // End of the address space.
+            };
+
+            let local_io_vector = libc::iovec {
+                iov_base: buf[total..].as_mut_ptr() as *mut c_void,
+                iov_len: buf.len() - total,
+            };
+
+            let report = match self.io_vectored_at_report(
+                process_vm_readv,
+                chunk_address,
+                &[local_io_vector],
+                (buf.len() - total) as u64,
+            ) {
+                Ok(report) => report,
+                Err(_) if total != 0 => break,
+                Err(err) => return Err(err),
+            };
+
+            total += report.transferred;
+            if !report.coverage_limited || report.transferred == 0 {
+                break;
+            }
+        }
+        Ok(total)
+    }
+
+    /// Read data as done by [`read_raw`](Self::read_raw), and also return the
+    /// memory region containing the address where reading started.
+    ///
+    /// The memory region is looked up in the cache maintained by
+    /// [`region_containing`](Self::region_containing), so it might be stale.
+    /// It tells, e.g., whether the data was read from executable code.
+    pub fn read_with_region(&mut self, buf: &mut [u8]) -> Result<(usize, Option<MemoryRegion>)> {
+        let region = match self.address {
+            None => None,
+            Some(address) => self.region_containing(address)?.cloned(),
+        };
+
+        let count = self.read_raw(buf)?;
+        Ok((count, region))
+    }
+
+    /// Advance the current address to the start of the next readable memory
+    /// region, if skipping holes is enabled and that region is close enough.
+    ///
+    /// The next readable memory region is looked up in the cache maintained
+    /// by [`region_containing`](Self::region_containing). If that finds no
+    /// region to skip to, then the cache might be stale, so it is refreshed
+    /// once, and looked up again.
+    ///
+    /// Returns `true` if the current address was advanced.
+    fn skip_hole(&mut self) -> Result<bool> {
+        let (Some(max_skip), Some(address)) = (self.max_hole_skip, self.address) else {
+            return Ok(false);
+        };
+
+        let mut refreshed = self.maps_cache.is_none();
+        if refreshed {
+            self.refresh_maps()?;
+        }
+
+        loop {
+            let regions = self.maps_cache.as_deref().unwrap_or_default();
+            let index = regions.partition_point(|region| region.end <= address);
+            let next_start = regions[index..]
+                .iter()
+                .find(|region| region.perms.read)
+                .map(|region| cmp::max(region.start, address));
+
+            match next_start {
+                Some(next_start) if next_start != address && next_start - address <= max_skip => {
+                    self.skipped_bytes += next_start - address;
+                    self.address = Some(next_start);
+                    return Ok(true);
+                }
+                _ if refreshed => return Ok(false),
+                _ => {
+                    self.refresh_maps()?;
+                    refreshed = true;
+                }
+            }
+        }
+    }
+
+    /// Write data from `buf` to the virtual memory of the target process at the
+    /// current address, then advance the current address by the number of
+    /// bytes written.
+    ///
+    /// This behaves like [`Write::write`], but returns an [`Error`] instead of
+    /// an [`io::Error`], which avoids extracting the [`Error`] out of the
+    /// [`io::Error`] to get its details.
+    ///
+    /// # Short writes
+    ///
+    /// The address range is transferred page by page, and the system stops
+    /// at the first page that cannot be written, without writing part of it.
+    /// A short count `n` therefore means that exactly the first `n` bytes of
+    /// `buf` were written, and that the current address was advanced to the
+    /// first page that cannot be written. The next write then fails, instead
+    /// of skipping that page.
+    pub fn write_raw(&mut self, buf: &[u8]) -> Result<usize> {
+        if let Some(address) = self.address {
+            self.check_writable(address, buf.len())?;
+        }
+
         let local_io_vector = libc::iovec {
             iov_base: buf.as_ptr() as *mut c_void,
             iov_len: buf.len(),
         };
 
-        self.io_vectored(libc::process_vm_readv, &[local_io_vector], buf.len() as u64)
-            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+        self.io_vectored(process_vm_writev, &[local_io_vector], buf.len() as u64)
     }
 
-    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
-        let bytes_to_read = bufs.iter().map(|buf| buf.len() as u64).sum();
-        let local_io_vectors =
-            unsafe { slice::from_raw_parts(bufs.as_ptr() as *const _, bufs.len()) };
+    /// Read data from the virtual memory of the target process at the current
+    /// address into the single buffer `buf`, as done by
+    /// [`read_raw`](Self::read_raw).
+    ///
+    /// This spares converting `buf` into a slice when the caller already
+    /// works with vectored I/O types.
+    pub fn read_io_slice(&mut self, buf: &mut IoSliceMut<'_>) -> Result<usize> {
+        self.read_raw(buf)
+    }
 
-        self.io_vectored(libc::process_vm_readv, local_io_vectors, bytes_to_read)
-            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+    /// Write data from the single buffer `buf` to the virtual memory of the
+    /// target process at the current address, as done by
+    /// [`write_raw`](Self::write_raw).
+    ///
+    /// This spares converting `buf` into a slice when the caller already
+    /// works with vectored I/O types.
+    pub fn write_io_slice(&mut self, buf: &IoSlice<'_>) -> Result<usize> {
+        self.write_raw(buf)
     }
-}
 
-impl Write for ProcessVirtualMemoryIO {
-    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+    /// Read data from the virtual memory of the target process at `address`
+    /// into `buf`.
+    ///
+    /// This behaves like [`read_raw`](Self::read_raw), i.e., it issues one
+    /// system call, unless enabled by
+    /// [`set_auto_continue`](Self::set_auto_continue), and may read less than
+    /// `buf.len()` bytes, but it does not skip holes, as configured by
+    /// [`set_auto_skip_holes`](Self::set_auto_skip_holes).
+    /// The current address is neither used nor modified.
+    pub fn read_at(&mut self, address: u64, buf: &mut [u8]) -> Result<usize> {
+        let result = if self.auto_continue {
+            self.read_continued_at(address, buf)
+        } else {
+            let local_io_vector = libc::iovec {
+                iov_base: buf.as_mut_ptr() as *mut c_void,
+                iov_len: buf.len(),
+            };
+
+            self.io_vectored_at(
+                process_vm_readv,
+                address,
+                &[local_io_vector],
+                buf.len() as u64,
+            )
+        };
+        result.map_err(|err| self.invalid_address_error(err, address))
+    }
+
+    /// Write data from `buf` to the virtual memory of the target process at
+    /// `address`.
+    ///
+    /// This behaves like [`write_raw`](Self::write_raw), i.e., it issues one
+    /// system call and may write less than `buf.len()` bytes.
+    /// The current address is neither used nor modified.
+    pub fn write_at(&mut self, address: u64, buf: &[u8]) -> Result<usize> {
+        self.check_writable(address, buf.len())?;
+
         let local_io_vector = libc::iovec {
             iov_base: buf.as_ptr() as *mut c_void,
             iov_len: buf.len(),
         };
 
-        self.io_vectored(
-            libc::process_vm_writev,
+        self.io_vectored_at(
+            process_vm_writev,
+            address,
             &[local_io_vector],
             buf.len() as u64,
         )
-        .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+        .map_err(|err| self.invalid_address_error(err, address))
     }
 
-    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
-        let bytes_to_write = bufs.iter().map(|buf| buf.len() as u64).sum();
+    /// Read data from the given `remote` address ranges of the virtual memory
+    /// of the target process into `local`, and return the number of bytes
+    /// read.
+    ///
+    /// Each address range is given by its address and its size in bytes. The
+    /// data of the address ranges, in order, is scattered into the buffers of
+    /// `local`, in order, as done by one `process_vm_readv()` system call,
+    /// which reads several discontiguous address ranges at once. More than
+    /// `IOV_MAX` address ranges or buffers are transferred in batches, with
+    /// one system call per batch.
+    ///
+    /// Reading stops when either all address ranges are read or all buffers
+    /// are full, or at the first address that cannot be read, and the number
+    /// of bytes read tells where. If nothing can be read at all, then an error
+    /// is returned. Address ranges are transferred as given, regardless of
+    /// [`set_page_budget`](Self::set_page_budget).
+    /// The current address is neither used nor modified.
+    pub fn read_scattered(
+        &mut self,
+        remote: &[(u64, usize)],
+        local: &mut [IoSliceMut<'_>],
+    ) -> Result<usize> {
+        let local_io_vectors =
+            unsafe { slice::from_raw_parts(local.as_ptr() as *const libc::iovec, local.len()) };
+
+        self.scattered_io(process_vm_readv, remote, local_io_vectors)
+    }
+
+    /// Write data from `local` to the given `remote` address ranges of the
+    /// virtual memory of the target process, and return the number of bytes
+    /// written.
+    ///
+    /// This is the counterpart of [`read_scattered`](Self::read_scattered):
+    /// the data of the buffers of `local`, in order, is gathered into the
+    /// address ranges, in order, as done by one `process_vm_writev()` system
+    /// call, or one per batch of at most `IOV_MAX` address ranges or buffers.
+    /// The writability of the first address range is checked if enabled by
+    /// [`set_check_writability`](Self::set_check_writability).
+    /// The current address is neither used nor modified.
+    pub fn write_scattered(
+        &mut self,
+        remote: &[(u64, usize)],
+        local: &[IoSlice<'_>],
+    ) -> Result<usize> {
+        if let Some(&(address, len)) = remote.iter().find(|(_, len)| *len != 0) {
+            self.check_writable(address, len)?;
+        }
+
+        let local_io_vectors =
+            unsafe { slice::from_raw_parts(local.as_ptr() as *const libc::iovec, local.len()) };
+
+        self.scattered_io(process_vm_writev, remote, local_io_vectors)
+    }
+
+    /// Transfer data between the `local_io_vectors` and the `remote` address
+    /// ranges of the virtual memory of the target process, as done by
+    /// [`read_scattered`](Self::read_scattered) and
+    /// [`write_scattered`](Self::write_scattered).
+    fn scattered_io(
+        &self,
+        process_vm_io_v: ProcessVMReadVProc,
+        remote: &[(u64, usize)],
+        local_io_vectors: &[libc::iovec],
+    ) -> Result<usize> {
+        let remote_size = remote
+            .iter()
+            .try_fold(0_usize, |total, &(_, len)| total.checked_add(len))
+            .ok_or_else(|| {
+                Error::from_io3(
+                    io::ErrorKind::InvalidInput.into(),
+                    system_call_name(process_vm_io_v, Backend::Syscall),
+                    self.process_id,
+                )
+            })?;
+
+        let local_size = local_io_vectors.iter().fold(0_usize, |total, io_vector| {
+            total.saturating_add(io_vector.iov_len)
+        });
+
+        if remote_size == 0 || local_size == 0 {
+            // No system call for empty address ranges or buffers.
+            return Ok(0);
+        }
+
+        let remote_io_vectors = remote
+            .iter()
+            .map(|&(address, len)| {
+                Ok(libc::iovec {
+                    iov_base: usize::try_from(address)? as *mut c_void,
+                    iov_len: len,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.auto_revalidate()?;
+
+        let (result, backend) = self.recorded_transfer(
+            Self::transfer_remote_io_vector_batches,
+            process_vm_io_v,
+            local_io_vectors,
+            &remote_io_vectors,
+        );
+
+        result.map_err(|err| {
+            Error::from_io3(
+                err,
+                system_call_name(process_vm_io_v, backend),
+                self.process_id,
+            )
+        })
+    }
+
+    /// Read data from the virtual memory of the target process at the current
+    /// address into `bufs`, as done by [`Read::read_vectored`], and report how
+    /// the transfer was limited.
+    ///
+    /// A transfer covers at most a limited number of pages. If the report
+    /// tells that the transfer was limited so, then reading less data than
+    /// requested does not mean that the next address is not readable, and
+    /// reading should be repeated to read the remaining data.
+    pub fn read_vectored_report(&mut self, bufs: &mut [IoSliceMut<'_>]) -> Result<TransferReport> {
+        let bytes_to_read = bufs.iter().map(|buf| buf.len() as u64).sum();
+        if bytes_to_read == 0 {
+            // No system call for empty or all-empty buffers.
+            return Ok(TransferReport::default());
+        }
+
+        let local_io_vectors =
+            unsafe { slice::from_raw_parts(bufs.as_ptr() as *const _, bufs.len()) };
+
+        self.io_vectored_report(process_vm_readv, local_io_vectors, bytes_to_read)
+    }
+
+    /// Read exactly `buf.len()` bytes from the virtual memory of the target
+    /// process at `address` into `buf`, repeating the transfer until `buf` is
+    /// full.
+    ///
+    /// This is the positioned counterpart of [`Read::read_exact`]. A system
+    /// call transfers at most what its `iovec`s cover, so transfers are
+    /// repeated from the first address not transferred yet. If a transfer
+    /// reads nothing, e.g., at the end of the address space, then an error of
+    /// kind [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) is returned;
+    /// if an address cannot be read, then the error reported by the system is
+    /// returned. In both cases, the contents of `buf` are unspecified.
+    /// The current address is neither used nor modified.
+    pub fn read_exact_at(&mut self, address: u64, buf: &mut [u8]) -> Result<()> {
+        self.transfer_all_at(
+            process_vm_readv,
+            address,
+            buf.as_mut_ptr(),
+            buf.len(),
+            io::ErrorKind::UnexpectedEof,
+        )
+    }
+
+    /// Write all of `buf` to the virtual memory of the target process at
+    /// `address`, repeating the transfer until `buf` is entirely written.
+    ///
+    /// This is the positioned counterpart of [`Write::write_all`]. If a
+    /// transfer writes nothing, then an error of kind
+    /// [`WriteZero`](io::ErrorKind::WriteZero) is returned; if an address
+    /// cannot be written, then the error reported by the system is returned.
+    /// In both cases, a prefix of `buf` might have been written.
+    /// The current address is neither used nor modified.
+    pub fn write_all_at(&mut self, address: u64, buf: &[u8]) -> Result<()> {
+        self.check_writable(address, buf.len())?;
+        self.transfer_all_at(
+            process_vm_writev,
+            address,
+            buf.as_ptr() as *mut u8,
+            buf.len(),
+            io::ErrorKind::WriteZero,
+        )
+    }
+
+    /// Read data from the virtual memory of the target process at the current
+    /// address into `bufs`, repeating the transfer until all buffers are full,
+    /// and advance the current address by the number of bytes read.
+    ///
+    /// This is the vectored counterpart of [`Read::read_exact`]. If the data
+    /// cannot be entirely read, e.g., because a part of it is not mapped, then
+    /// an error of kind [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) is
+    /// returned, and the contents of `bufs` are unspecified.
+    pub fn read_exact_vectored(&mut self, mut bufs: &mut [IoSliceMut<'_>]) -> Result<()> {
+        IoSliceMut::advance_slices(&mut bufs, 0);
+
+        while !bufs.is_empty() {
+            let bytes_to_read = bufs.iter().map(|buf| buf.len() as u64).sum();
+            let local_io_vectors =
+                unsafe { slice::from_raw_parts(bufs.as_ptr() as *const _, bufs.len()) };
+
+            match self.io_vectored(process_vm_readv, local_io_vectors, bytes_to_read) {
+                Ok(0) => break,
+                Ok(count) => IoSliceMut::advance_slices(&mut bufs, count),
+                Err(err) if err.os_error_code() == Some(libc::EFAULT) => break,
+                Err(err) => return Err(err),
+            }
+        }
+
+        if bufs.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::from_io3(
+                io::ErrorKind::UnexpectedEof.into(),
+                "process_vm_readv",
+                self.process_id,
+            ))
+        }
+    }
+
+    /// Read an unsigned 16-bit integer stored with the given byte order from the
+    /// virtual memory of the target process at the current address, then
+    /// advance the current address by the number of bytes read.
+    ///
+    /// If the integer cannot be entirely read, then an error of kind
+    /// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof) is returned, as done by
+    /// [`read_exact_vectored`](Self::read_exact_vectored).
+    pub fn read_u16(&mut self, endianness: Endianness) -> Result<u16> {
+        let bytes = self.read_array::<2>()?;
+        Ok(endianness.decode_u64(&bytes) as u16)
+    }
+
+    /// Read an unsigned 32-bit integer, as done by
+    /// [`read_u16`](Self::read_u16).
+    pub fn read_u32(&mut self, endianness: Endianness) -> Result<u32> {
+        let bytes = self.read_array::<4>()?;
+        Ok(endianness.decode_u64(&bytes) as u32)
+    }
+
+    /// Read an unsigned 64-bit integer, as done by
+    /// [`read_u16`](Self::read_u16).
+    pub fn read_u64(&mut self, endianness: Endianness) -> Result<u64> {
+        let bytes = self.read_array::<8>()?;
+        Ok(endianness.decode_u64(&bytes))
+    }
+
+    /// Read a signed 16-bit integer, as done by [`read_u16`](Self::read_u16).
+    pub fn read_i16(&mut self, endianness: Endianness) -> Result<i16> {
+        self.read_u16(endianness).map(|value| value as i16)
+    }
+
+    /// Read a signed 32-bit integer, as done by [`read_u16`](Self::read_u16).
+    pub fn read_i32(&mut self, endianness: Endianness) -> Result<i32> {
+        self.read_u32(endianness).map(|value| value as i32)
+    }
+
+    /// Read a signed 64-bit integer, as done by [`read_u16`](Self::read_u16).
+    pub fn read_i64(&mut self, endianness: Endianness) -> Result<i64> {
+        self.read_u64(endianness).map(|value| value as i64)
+    }
+
+    /// Read exactly `N` bytes from the virtual memory of the target process at
+    /// the current address, then advance the current address by the number of
+    /// bytes read.
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut bytes = [0_u8; N];
+        self.read_exact_vectored(&mut [IoSliceMut::new(&mut bytes)])?;
+        Ok(bytes)
+    }
+
+    /// Perform vectored (i.e., scatter/gather) I/O on the virtual memory of the
+    /// target process, starting at the current address, then advance the
+    /// current address by the number of transferred bytes.
+    fn io_vectored(
+        &mut self,
+        process_vm_io_v: ProcessVMReadVProc,
+        local_io_vectors: &[libc::iovec],
+        byte_count: u64,
+    ) -> Result<usize> {
+        self.io_vectored_report(process_vm_io_v, local_io_vectors, byte_count)
+            .map(|report| report.transferred)
+    }
+
+    /// Perform vectored I/O as done by [`io_vectored`](Self::io_vectored), and
+    /// report how the transfer was limited.
+    fn io_vectored_report(
+        &mut self,
+        process_vm_io_v: ProcessVMReadVProc,
+        local_io_vectors: &[libc::iovec],
+        byte_count: u64,
+    ) -> Result<TransferReport> {
+        let address = match self.address {
+            None => return Ok(TransferReport::default()),
+            Some(address) => address,
+        };
+
+        let report = self
+            .io_vectored_at_report(process_vm_io_v, address, local_io_vectors, byte_count)
+            .map_err(|err| self.invalid_address_error(err, address))?;
+        let transferred_bytes_count = report.transferred;
+
+        // Do not overflow the address space.
+        let max_remaining_bytes = (u64::MAX - address).saturating_add(1);
+
+        self.address = if (transferred_bytes_count as u64) < max_remaining_bytes {
+            Some(address + (transferred_bytes_count as u64))
+        } else {
+            None // End of file (actually, address space).
+        };
+
+        Ok(report)
+    }
+
+    /// Perform vectored (i.e., scatter/gather) I/O on the virtual memory of the
+    /// target process, starting at the given address.
+    ///
+    /// The current address is neither used nor modified.
+    fn io_vectored_at(
+        &self,
+        process_vm_io_v: ProcessVMReadVProc,
+        address: u64,
+        local_io_vectors: &[libc::iovec],
+        byte_count: u64,
+    ) -> Result<usize> {
+        self.io_vectored_at_report(process_vm_io_v, address, local_io_vectors, byte_count)
+            .map(|report| report.transferred)
+    }
+
+    /// Perform vectored I/O as done by [`io_vectored_at`](Self::io_vectored_at),
+    /// and report how the transfer was limited.
+    fn io_vectored_at_report(
+        &self,
+        process_vm_io_v: ProcessVMReadVProc,
+        address: u64,
+        local_io_vectors: &[libc::iovec],
+        mut byte_count: u64,
+    ) -> Result<TransferReport> {
+        if byte_count == 0 {
+            return Ok(TransferReport::default());
+        }
+
+        // Do not overflow the address space.
+        let max_remaining_bytes = (u64::MAX - address).saturating_add(1);
+        byte_count = cmp::min(byte_count, max_remaining_bytes);
+        let requested_byte_count = byte_count;
+
+        byte_count = self.limit_bytes_per_call(byte_count);
+
+        let (remote_io_vectors, size_of_not_covered_suffix) =
+            self.remote_io_vectors(address, byte_count)?;
+        let uncovered = (requested_byte_count - byte_count) + size_of_not_covered_suffix;
+
+        self.auto_revalidate()?;
+
+        let (result, backend) = self.recorded_transfer(
+            Self::transfer_io_vector_batches,
+            process_vm_io_v,
+            local_io_vectors,
+            &remote_io_vectors,
+        );
+
+        result
+            .map(|transferred| TransferReport {
+                transferred,
+                coverage_limited: uncovered != 0,
+                uncovered,
+            })
+            .map_err(|err| {
+                let operation = match backend {
+                    Backend::Syscall => "process_vm_readv/process_vm_writev",
+                    Backend::ProcMem => "pread/pwrite(/proc/[pid]/mem)",
+                };
+                Error::from_io3(err, operation, self.process_id)
+            })
+    }
+
+    /// Transfer data between the `local_io_vectors` and the
+    /// `remote_io_vectors` of the target process with `transfer`, then record
+    /// the outcome: the backend of a successful transfer, the
+    /// [transfer histogram](Self::transfer_histogram), and the
+    /// [trace hook](Self::set_trace_hook), if any.
+    /// Returns the number of bytes transferred, and the backend used.
+    fn recorded_transfer(
+        &self,
+        transfer: TransferIoVectorsProc,
+        process_vm_io_v: ProcessVMReadVProc,
+        local_io_vectors: &[libc::iovec],
+        remote_io_vectors: &[libc::iovec],
+    ) -> (io::Result<usize>, Backend) {
+        let start_time = self.trace_hook.as_ref().map(|_| Instant::now());
+
+        let (result, backend) =
+            transfer(self, process_vm_io_v, local_io_vectors, remote_io_vectors);
+
+        if result.is_ok() {
+            self.set_last_backend(backend);
+        }
+
+        #[cfg(feature = "metrics")]
+        if let Ok(count) = result {
+            let bucket = (usize::BITS - count.leading_zeros()).saturating_sub(1);
+            self.transfer_histogram[bucket as usize].fetch_add(1, atomic::Ordering::Relaxed);
+        }
+
+        if let (Some(hook), Some(start_time)) = (&self.trace_hook, start_time) {
+            let duration = start_time.elapsed();
+            let size_of = |io_vectors: &[libc::iovec]| {
+                io_vectors.iter().fold(0_u64, |total, io_vector| {
+                    total.saturating_add(io_vector.iov_len as u64)
+                })
+            };
+            let address = remote_io_vectors
+                .iter()
+                .find(|io_vector| io_vector.iov_len != 0)
+                .map_or(0, |io_vector| io_vector.iov_base as u64);
+
+            hook.call(TraceEvent {
+                operation: system_call_name(process_vm_io_v, backend),
+                process_id: self.process_id as u32,
+                address,
+                requested_bytes: cmp::min(size_of(local_io_vectors), size_of(remote_io_vectors)),
+                transferred_bytes: *result.as_ref().unwrap_or(&0),
+                errno: result.as_ref().err().and_then(io::Error::raw_os_error),
+                duration,
+                in_caller_stack: self.in_caller_stack(remote_io_vectors),
+            });
+        }
+
+        (result, backend)
+    }
+
+    /// Return `byte_count`, limited to the configured maximum number of bytes
+    /// transferred by one system call, if any.
+    fn limit_bytes_per_call(&self, byte_count: u64) -> u64 {
+        match self.max_bytes_per_call {
+            None => byte_count,
+            Some(max_bytes) => cmp::min(byte_count, max_bytes.get()),
+        }
+    }
+
+    /// Return the remote `iovec`s describing the address range of `byte_count`
+    /// bytes at `address`, which must not be empty, and the size of the suffix
+    /// of that range that they do not cover, if any.
+    ///
+    /// The system transfers data page by page, and stops at the first page that
+    /// cannot be transferred, even in the middle of an `iovec`. Splitting the
+    /// address range into one `iovec` per page is therefore needed only to
+    /// bound the number of pages accessed by one system call. If the address
+    /// range spans few enough pages, then it is described by one `iovec`.
+    fn remote_io_vectors(
+        &self,
+        address: u64,
+        byte_count: u64,
+    ) -> Result<(SmallVec<[libc::iovec; 3]>, u64)> {
+        let max_iov_count = self.max_iov_count();
+
+        let first_page = align_down(address, self.page_size);
+        let last_page = align_down(address + (byte_count - 1), self.page_size);
+        let page_count = (last_page - first_page) / self.page_size + 1;
+
+        if page_count <= max_iov_count as u64 {
+            let mut result = SmallVec::new();
+            result.push(libc::iovec {
+                iov_base: usize::try_from(address)? as *mut c_void,
+                iov_len: usize::try_from(byte_count)?,
+            });
+            return Ok((result, 0));
+        }
+
+        PageAwareAddressRange::with_page_size(address, byte_count, self.page_size)
+            .into_iov_buffers(self.page_size, max_iov_count)
+    }
+
+    /// Transfer data between the `local_io_vectors` and the
+    /// `remote_io_vectors` of the target process, as done by
+    /// [`transfer_io_vector_batches`](Self::transfer_io_vector_batches), in
+    /// batches of at most `IOV_MAX` remote `iovec`s.
+    ///
+    /// Each batch of remote `iovec`s is paired with the local `iovec`s holding
+    /// as many bytes, in the same way.
+    /// Returns the number of bytes transferred, and the backend used.
+    fn transfer_remote_io_vector_batches(
+        &self,
+        process_vm_io_v: ProcessVMReadVProc,
+        local_io_vectors: &[libc::iovec],
+        remote_io_vectors: &[libc::iovec],
+    ) -> (io::Result<usize>, Backend) {
+        let iov_max = self.iov_max.get();
+        if remote_io_vectors.len() <= iov_max {
+            return self.transfer_io_vector_batches(
+                process_vm_io_v,
+                local_io_vectors,
+                remote_io_vectors,
+            );
+        }
+
+        let mut local_parts = IoVectorParts::new(local_io_vectors);
+        let mut batch_local_io_vectors = Vec::new();
+        let mut total = 0;
+        let mut backend = Backend::Syscall;
+
+        for batch_remote_io_vectors in remote_io_vectors.chunks(iov_max) {
+            let batch_size: usize = batch_remote_io_vectors
+                .iter()
+                .map(|io_vector| io_vector.iov_len)
+                .sum();
+            if batch_size == 0 {
+                continue;
+            }
+
+            batch_local_io_vectors.clear();
+            if local_parts.take(batch_size, &mut batch_local_io_vectors) == 0 {
+                break;
+            }
+
+            let (result, batch_backend) = self.transfer_io_vector_batches(
+                process_vm_io_v,
+                &batch_local_io_vectors,
+                batch_remote_io_vectors,
+            );
+            backend = batch_backend;
+
+            match result {
+                Ok(count) => {
+                    total += count;
+                    if count < batch_size {
+                        break;
+                    }
+                }
+                Err(err) if total == 0 => return (Err(err), backend),
+                Err(_) => break,
+            }
+        }
+        (Ok(total), backend)
+    }
+
+    /// Transfer data between the `local_io_vectors` and the
+    /// `remote_io_vectors` of the target process, as done by
+    /// [`transfer_io_vectors`](Self::transfer_io_vectors), in batches of at
+    /// most `IOV_MAX` local `iovec`s.
+    ///
+    /// Each batch of local `iovec`s is paired with the remote `iovec`s holding
+    /// as many bytes, splitting a remote `iovec` that straddles two batches.
+    /// Transferring stops at the first batch that is not entirely transferred,
+    /// and a failure after some data was transferred is reported as a short
+    /// count, as the system does within one transfer.
+    /// Returns the number of bytes transferred, and the backend used.
+    fn transfer_io_vector_batches(
+        &self,
+        process_vm_io_v: ProcessVMReadVProc,
+        local_io_vectors: &[libc::iovec],
+        remote_io_vectors: &[libc::iovec],
+    ) -> (io::Result<usize>, Backend) {
+        let iov_max = self.iov_max.get();
+        if local_io_vectors.len() <= iov_max {
+            return self.transfer_io_vectors(process_vm_io_v, local_io_vectors, remote_io_vectors);
+        }
+
+        let mut remote_parts = IoVectorParts::new(remote_io_vectors);
+        let mut batch_remote_io_vectors = Vec::with_capacity(remote_io_vectors.len());
+        let mut total = 0;
+        let mut backend = Backend::Syscall;
+
+        for batch_local_io_vectors in local_io_vectors.chunks(iov_max) {
+            let batch_size: usize = batch_local_io_vectors
+                .iter()
+                .map(|io_vector| io_vector.iov_len)
+                .sum();
+
+            batch_remote_io_vectors.clear();
+            if remote_parts.take(batch_size, &mut batch_remote_io_vectors) == 0 {
+                break;
+            }
+
+            let (result, batch_backend) = self.transfer_io_vectors(
+                process_vm_io_v,
+                batch_local_io_vectors,
+                &batch_remote_io_vectors,
+            );
+            backend = batch_backend;
+
+            match result {
+                Ok(count) => {
+                    total += count;
+                    if count < batch_size {
+                        break;
+                    }
+                }
+                Err(err) if total == 0 => return (Err(err), backend),
+                Err(_) => break,
+            }
+        }
+        (Ok(total), backend)
+    }
+
+    /// Transfer data between the `local_io_vectors` and the
+    /// `remote_io_vectors` of the target process, as done by
+    /// [`transfer_io_vectors_unverified`](Self::transfer_io_vectors_unverified),
+    /// then check that the target process did not exit, if enabled by
+    /// [`from_pidfd`](Self::from_pidfd).
+    /// Returns the number of bytes transferred, and the backend used.
+    fn transfer_io_vectors(
+        &self,
+        process_vm_io_v: ProcessVMReadVProc,
+        local_io_vectors: &[libc::iovec],
+        remote_io_vectors: &[libc::iovec],
+    ) -> (io::Result<usize>, Backend) {
+        let (result, backend) = self.transfer_io_vectors_unverified(
+            process_vm_io_v,
+            local_io_vectors,
+            remote_io_vectors,
+        );
+
+        match result {
+            Ok(_) if self.verify_process_fd => match self.ensure_process_fd_alive() {
+                Ok(()) => (result, backend),
+                Err(err) => (Err(err), backend),
+            },
+            result => (result, backend),
+        }
+    }
+
+    /// Fail with an `ESRCH` errno code if the process referred to by
+    /// [`process_fd`](Self::process_fd), if opened, exited, i.e., if that file
+    /// descriptor is readable.
+    fn ensure_process_fd_alive(&self) -> io::Result<()> {
+        let Some(fd) = self.process_fd.get() else {
+            return Ok(());
+        };
+
+        let mut poll_fd = libc::pollfd {
+            fd: fd.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        loop {
+            match unsafe { libc::poll(&mut poll_fd, 1, 0) } {
+                -1 => {
+                    let err = io::Error::last_os_error();
+                    if err.kind() != io::ErrorKind::Interrupted {
+                        return Err(err);
+                    }
+                }
+                0 => return Ok(()),
+                _ => return Err(io::Error::from_raw_os_error(libc::ESRCH)),
+            }
+        }
+    }
+
+    /// Transfer data between the `local_io_vectors` and the
+    /// `remote_io_vectors` of the target process, with `process_vm_io_v`.
+    ///
+    /// If the system does not support `process_vm_io_v`, i.e., it fails with
+    /// an `ENOSYS` errno code, then this and all later transfers use the
+    /// `/proc/[pid]/mem` file of the target process instead.
+    /// Returns the number of bytes transferred, and the backend used.
+    fn transfer_io_vectors_unverified(
+        &self,
+        process_vm_io_v: ProcessVMReadVProc,
+        local_io_vectors: &[libc::iovec],
+        remote_io_vectors: &[libc::iovec],
+    ) -> (io::Result<usize>, Backend) {
+        if !self.proc_mem_fallback.load(atomic::Ordering::Relaxed) {
+            let transferred_bytes_count = unsafe {
+                process_vm_io_v(
+                    self.process_id,
+                    local_io_vectors.as_ptr(),
+                    local_io_vectors.len() as c_ulong,
+                    remote_io_vectors.as_ptr(),
+                    remote_io_vectors.len() as c_ulong,
+                    0,
+                )
+            };
+
+            if transferred_bytes_count != -1 {
+                return (Ok(transferred_bytes_count as usize), Backend::Syscall);
+            }
+
+            let err = io::Error::last_os_error();
+            if err.raw_os_error() != Some(libc::ENOSYS) {
+                return (Err(err), Backend::Syscall);
+            }
+            self.proc_mem_fallback
+                .store(true, atomic::Ordering::Relaxed);
+        }
+
+        let result = self.proc_mem_io_v(
+            is_write(process_vm_io_v),
+            local_io_vectors,
+            remote_io_vectors,
+        );
+        (result, Backend::ProcMem)
+    }
+
+    /// Transfer data between the `local_io_vectors` and the
+    /// `remote_io_vectors` of the target process, through the
+    /// `/proc/[pid]/mem` file of the target process.
+    ///
+    /// This behaves like `process_vm_readv()` and `process_vm_writev()`: the
+    /// transfer stops at the first remote `iovec` that cannot be entirely
+    /// transferred, and fails with an `EFAULT` errno code if no data could be
+    /// transferred at all.
+    fn proc_mem_io_v(
+        &self,
+        write: bool,
+        local_io_vectors: &[libc::iovec],
+        remote_io_vectors: &[libc::iovec],
+    ) -> io::Result<usize> {
+        let fd = self.proc_mem_file()?.as_raw_fd();
+
+        let mut local_parts = local_io_vectors
+            .iter()
+            .filter(|io_vector| io_vector.iov_len != 0)
+            .map(|io_vector| (io_vector.iov_base as *mut u8, io_vector.iov_len));
+        let mut local_part = (ptr::null_mut(), 0);
+        let mut total = 0;
+
+        for remote_io_vector in remote_io_vectors {
+            let mut address = remote_io_vector.iov_base as u64;
+            let mut remaining = remote_io_vector.iov_len;
+
+            while remaining != 0 {
+                if local_part.1 == 0 {
+                    match local_parts.next() {
+                        Some(part) => local_part = part,
+                        None => return Ok(total),
+                    }
+                }
+
+                let size = cmp::min(local_part.1, remaining);
+                let offset = address as libc::off_t;
+                let count = unsafe {
+                    if write {
+                        libc::pwrite(fd, local_part.0 as *const c_void, size, offset)
+                    } else {
+                        libc::pread(fd, local_part.0 as *mut c_void, size, offset)
+                    }
+                };
+
+                if count <= 0 {
+                    let err = io::Error::last_os_error();
+                    if count == -1 && err.kind() == io::ErrorKind::Interrupted {
+                        continue;
+                    }
+                    return match total {
+                        // Inaccessible addresses cause `EIO` errors.
+                        0 if count == 0 || err.raw_os_error() == Some(libc::EIO) => {
+                            Err(io::Error::from_raw_os_error(libc::EFAULT))
+                        }
+                        0 => Err(err),
+                        _ => Ok(total),
+                    };
+                }
+
+                let count = count as usize;
+                total += count;
+                local_part = (local_part.0.wrapping_add(count), local_part.1 - count);
+                address += count as u64;
+                remaining -= count;
+                if count < size {
+                    // Stop at the first page that cannot be entirely transferred.
+                    return Ok(total);
+                }
+            }
+        }
+        Ok(total)
+    }
+
+    /// Return the `/proc/[pid]/mem` file of the target process, opened by the
+    /// first call to this method.
+    ///
+    /// The file is opened for reading and writing if permitted, otherwise for
+    /// reading only.
+    fn proc_mem_file(&self) -> io::Result<&File> {
+        if let Some(file) = self.proc_mem.get() {
+            return Ok(file);
+        }
+
+        let path = format!("/proc/{}/mem", self.process_id);
+        let file = match OpenOptions::new().read(true).write(true).open(&path) {
+            Err(err) if err.kind() == io::ErrorKind::PermissionDenied => File::open(&path)?,
+            result => result?,
+        };
+        Ok(self.proc_mem.get_or_init(|| file))
+    }
+
+    /// Return an error of kind [`ErrorKind::InvalidAddress`] instead of `err`
+    /// if `err` reports that no data could be transferred at `address`, where
+    /// a transfer started, with an `EFAULT` errno code, and if enabled by
+    /// [`Builder::invalid_address_errors`].
+    ///
+    /// Faults reported after some data was transferred indicate a gap in the
+    /// address range being transferred, not an invalid address, so they are
+    /// not passed to this method.
+    fn invalid_address_error(&self, err: Error, address: u64) -> Error {
+        if self.invalid_address_errors && err.os_error_code() == Some(libc::EFAULT) {
+            ErrorKind::InvalidAddress {
+                address,
+                process_id: self.process_id,
+            }
+            .into()
+        } else {
+            err
+        }
+    }
+
+    /// Return an error of kind [`ErrorKind::NotWritable`] if writing `len`
+    /// bytes at `address` would start in a cached memory region mapped without
+    /// write permission, and if enabled by
+    /// [`set_check_writability`](Self::set_check_writability).
+    fn check_writable(&mut self, address: u64, len: usize) -> Result<()> {
+        if !self.check_writability || len == 0 {
+            return Ok(());
+        }
+
+        match self.region_containing(address)? {
+            Some(region) if !region.perms.write => Err(ErrorKind::NotWritable {
+                address,
+                process_id: self.process_id,
+            }
+            .into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Determine whether any of the `remote_io_vectors` overlaps the stack of
+    /// the calling thread, unless allowed by [`Builder::allow_self_stack`].
+    ///
+    /// That stack changes as the calling thread runs, including during the
+    /// transfer, so such transfers are almost always a mistake.
+    #[cfg(debug_assertions)]
+    fn in_caller_stack(&self, remote_io_vectors: &[libc::iovec]) -> bool {
+        !self.allow_self_stack
+            && self.process_id as u32 == std::process::id()
+            && current_thread_stack().is_some_and(|stack| {
+                remote_io_vectors.iter().any(|io_vector| {
+                    let address = io_vector.iov_base as u64;
+                    address < stack.end
+                        && address.saturating_add(io_vector.iov_len as u64) > stack.start
+                })
+            })
+    }
+
+    /// Release builds do not check whether transfers overlap the stack of the
+    /// calling thread.
+    #[cfg(not(debug_assertions))]
+    fn in_caller_stack(&self, _remote_io_vectors: &[libc::iovec]) -> bool {
+        false
+    }
+
+    /// Transfer `size` bytes between the local buffer at `buffer` and the
+    /// virtual memory of the target process at `address`, repeating the
+    /// transfer until all bytes are transferred.
+    ///
+    /// If the transfer stops before all bytes are transferred, then an error
+    /// of kind `incomplete_kind` is returned.
+    fn transfer_all_at(
+        &self,
+        process_vm_io_v: ProcessVMReadVProc,
+        mut address: u64,
+        buffer: *mut u8,
+        size: usize,
+        incomplete_kind: io::ErrorKind,
+    ) -> Result<()> {
+        let mut total = 0;
+        while total < size {
+            let local_io_vector = libc::iovec {
+                iov_base: buffer.wrapping_add(total) as *mut c_void,
+                iov_len: size - total,
+            };
+
+            let count = match self.io_vectored_at(
+                process_vm_io_v,
+                address,
+                &[local_io_vector],
+                (size - total) as u64,
+            ) {
+                Ok(count) => count,
+                Err(err) if total == 0 => return Err(self.invalid_address_error(err, address)),
+                Err(err) => return Err(err),
+            };
+
+            total += count;
+            address = match address.checked_add(count as u64) {
+                Some(address) if count != 0 => address,
+                _ if total == size => break,
+                _ => {
+                    return Err(Error::from_io3(
+                        incomplete_kind.into(),
+                        "process_vm_readv/process_vm_writev",
+                        self.process_id,
+                    ))
+                }
+            };
+        }
+        Ok(())
+    }
+
+    /// Read data from the virtual memory of the target process at `address`
+    /// into `buf`, repeating the transfer until `buf` is full.
+    ///
+    /// Reading stops early, without failing, at the first address that cannot
+    /// be read. The number of bytes read is returned.
+    fn read_until_fault(&self, address: u64, buf: &mut [u8]) -> Result<usize> {
+        match self.read_at_most(address, buf) {
+            Err(err) if err.os_error_code() == Some(libc::EFAULT) => Ok(0),
+            result => result,
+        }
+    }
+
+    /// Read data from the virtual memory of the target process at `address`
+    /// into `buf`, repeating the transfer until `buf` is full.
+    ///
+    /// Unlike [`read`](Read::read), which transfers at most what one system
+    /// call can transfer, this method issues as many system calls as needed.
+    /// Reading stops early at the first address that cannot be read, and the
+    /// number of bytes read is returned. If no data can be read at all, then
+    /// an error is returned.
+    /// The current address is neither used nor modified.
+    pub fn read_large(&mut self, buf: &mut [u8], address: u64) -> Result<usize> {
+        self.read_at_most(address, buf)
+    }
+
+    /// Read data from the virtual memory of the target process at `address`
+    /// into `buf`, as done by [`read_large`](Self::read_large).
+    fn read_at_most(&self, address: u64, buf: &mut [u8]) -> Result<usize> {
+        self.transfer_at_most(process_vm_readv, address, buf.as_mut_ptr(), buf.len())
+    }
+
+    /// Write data from `buf` to the virtual memory of the target process at
+    /// `address`, repeating the transfer until `buf` is entirely written.
+    ///
+    /// Unlike [`write`](Write::write), which transfers at most what one system
+    /// call can transfer, this method issues as many system calls as needed.
+    /// Writing stops early at the first address that cannot be written, and
+    /// the number of bytes written is returned. If no data can be written at
+    /// all, then an error is returned.
+    /// The current address is neither used nor modified.
+    pub fn write_large(&mut self, buf: &[u8], address: u64) -> Result<usize> {
+        self.check_writable(address, buf.len())?;
+        self.transfer_at_most(
+            process_vm_writev,
+            address,
+            buf.as_ptr() as *mut u8,
+            buf.len(),
+        )
+    }
+
+    /// Write `len` copies of the byte `value` to the virtual memory of the
+    /// target process at `address`, like `memset()` does locally.
+    ///
+    /// Only one page of local memory is filled with `value`. Each system call
+    /// writes it repeatedly, by providing as many local `iovec`s referring to
+    /// it as there are pages to write. As many system calls as needed are
+    /// issued. Writing stops early at the first address that cannot be
+    /// written, and the number of bytes written is returned. If no data can be
+    /// written at all, then an error is returned.
+    /// The current address is neither used nor modified.
+    pub fn fill(&mut self, address: u64, value: u8, len: u64) -> Result<u64> {
+        self.check_writable(address, usize::try_from(len).unwrap_or(usize::MAX))?;
+
+        let page_size = usize::try_from(self.page_size)?;
+        let buffer = vec![value; page_size];
+        let local_io_vector = libc::iovec {
+            iov_base: buffer.as_ptr() as *mut c_void,
+            iov_len: page_size,
+        };
+        let local_io_vectors = vec![local_io_vector; self.max_iov_count()];
+        let max_chunk_size = self.page_size * local_io_vectors.len() as u64;
+
+        let mut total = 0_u64;
+        while total < len {
+            let Some(chunk_address) = address.checked_add(total) else {
+                break; // End of the address space.
+            };
+            let chunk_size = cmp::min(len - total, max_chunk_size);
+            let local_count = chunk_size.div_ceil(self.page_size) as usize;
+
+            let count = match self.io_vectored_at(
+                process_vm_writev,
+                chunk_address,
+                &local_io_vectors[..local_count],
+                chunk_size,
+            ) {
+                Ok(count) => count,
+                Err(err) if total == 0 => return Err(self.invalid_address_error(err, address)),
+                Err(err) if err.os_error_code() == Some(libc::EFAULT) => 0,
+                Err(err) => return Err(err),
+            };
+
+            if count == 0 {
+                break;
+            }
+            total += count as u64;
+        }
+        Ok(total)
+    }
+
+    /// Write `len` zero bytes to the virtual memory of the target process at
+    /// `address`, as done by [`fill`](Self::fill).
+    ///
+    /// The number of bytes written is returned.
+    /// The current address is neither used nor modified.
+    pub fn zero(&mut self, address: u64, len: u64) -> Result<u64> {
+        self.fill(address, 0, len)
+    }
+
+    /// Transfer at most `size` bytes between the local buffer at `buffer` and
+    /// the virtual memory of the target process at `address`, repeating the
+    /// transfer until all bytes are transferred, or until the first address
+    /// that cannot be accessed.
+    ///
+    /// If no data can be transferred at all, then an error is returned.
+    fn transfer_at_most(
+        &self,
+        process_vm_io_v: ProcessVMReadVProc,
+        mut address: u64,
+        buffer: *mut u8,
+        size: usize,
+    ) -> Result<usize> {
+        let mut total = 0;
+        while total < size {
+            let local_io_vector = libc::iovec {
+                iov_base: buffer.wrapping_add(total) as *mut c_void,
+                iov_len: size - total,
+            };
+
+            let count = match self.io_vectored_at(
+                process_vm_io_v,
+                address,
+                &[local_io_vector],
+                (size - total) as u64,
+            ) {
+                Ok(count) => count,
+                Err(err) if total == 0 => return Err(self.invalid_address_error(err, address)),
+                Err(err) if err.os_error_code() == Some(libc::EFAULT) => 0,
+                Err(err) => return Err(err),
+            };
+
+            if count == 0 {
+                break;
+            }
+
+            total += count;
+            address = match address.checked_add(count as u64) {
+                Some(address) => address,
+                None => break, // End of the address space.
+            };
+        }
+        Ok(total)
+    }
+
+    /// Read exactly `len` bytes from the virtual memory of the target process
+    /// at `address`.
+    ///
+    /// The returned data is stored inline, without allocating on the heap,
+    /// if `len` is not greater than `N`.
+    /// The current address is neither used nor modified.
+    pub fn read_small<const N: usize>(
+        &mut self,
+        address: u64,
+        len: usize,
+    ) -> Result<SmallVec<[u8; N]>> {
+        let mut result = SmallVec::from_elem(0_u8, len);
+        self.transfer_all_at(
+            process_vm_readv,
+            address,
+            result.as_mut_ptr(),
+            len,
+            io::ErrorKind::UnexpectedEof,
+        )?;
+        Ok(result)
+    }
+
+    /// Read exactly `len` bytes from the virtual memory of the target process
+    /// at `address`, into `out`, replacing its previous contents.
+    ///
+    /// The capacity of `out` is reused, so that reading repeatedly, e.g.,
+    /// polling a structure of the same size at varying addresses, does not
+    /// allocate once `out` is large enough. As many system calls as needed
+    /// are issued. If the data cannot be read completely, e.g., due to an
+    /// unmapped hole, then an error is returned and `out` is left empty.
+    /// The current address is neither used nor modified.
+    pub fn read_exact_at_into(
+        &mut self,
+        out: &mut Vec<u8>,
+        len: usize,
+        address: u64,
+    ) -> Result<()> {
+        out.clear();
+        out.resize(len, 0);
+        self.transfer_all_at(
+            process_vm_readv,
+            address,
+            out.as_mut_ptr(),
+            len,
+            io::ErrorKind::UnexpectedEof,
+        )
+        .inspect_err(|_err| out.clear())
+    }
+
+    /// Read exactly `len` bytes from the virtual memory of the target process
+    /// at `address`, into a [`Bytes`](bytes::Bytes) buffer.
+    ///
+    /// The returned buffer can be sliced and shared without copying its data.
+    /// The current address is neither used nor modified.
+    #[cfg(feature = "bytes")]
+    pub fn read_bytes(&mut self, address: u64, len: usize) -> Result<bytes::Bytes> {
+        let mut result = bytes::BytesMut::zeroed(len);
+        self.transfer_all_at(
+            process_vm_readv,
+            address,
+            result.as_mut_ptr(),
+            len,
+            io::ErrorKind::UnexpectedEof,
+        )?;
+        Ok(result.freeze())
+    }
+
+    /// Read a NUL-terminated string of bytes, e.g., a C `char*` string, from
+    /// the virtual memory of the target process at `address`.
+    ///
+    /// At most `max_len` bytes are read, not counting the NUL terminator.
+    /// The returned [`RemoteString`] tells whether the NUL terminator was
+    /// reached, or whether the string was truncated to `max_len` bytes.
+    /// Data is read page by page, so that reading does not fail if the string
+    /// ends before an inaccessible page. If an address cannot be read before
+    /// the NUL terminator, then an error is returned.
+    /// The current address is neither used nor modified.
+    pub fn read_cstr(&mut self, address: u64, max_len: usize) -> Result<RemoteString<u8>> {
+        let (data, terminated) = self.read_nul_terminated(address, 1, max_len)?;
+        Ok(if terminated {
+            RemoteString::Terminated(data)
+        } else {
+            RemoteString::Truncated(data)
+        })
+    }
+
+    /// Read a NUL-terminated UTF-16 string, e.g., a Windows `wchar_t*` string,
+    /// from the virtual memory of the target process at `address`.
+    ///
+    /// At most `max_units` code units are read, not counting the NUL
+    /// terminator. As for [`read_cstr`](Self::read_cstr), the returned
+    /// [`RemoteString`] tells whether the NUL terminator was reached. Code
+    /// units are decoded according to the configured
+    /// [byte order](Self::set_endianness), and are not validated. Data is
+    /// read page by page, as done by [`read_cstr`](Self::read_cstr).
+    /// The current address is neither used nor modified.
+    pub fn read_utf16_cstr(&mut self, address: u64, max_units: usize) -> Result<RemoteString<u16>> {
+        let (data, terminated) = self.read_nul_terminated(address, 2, max_units)?;
+        let units = data
+            .chunks_exact(2)
+            .map(|unit| self.endianness.decode_u64(unit) as u16)
+            .collect();
+
+        Ok(if terminated {
+            RemoteString::Terminated(units)
+        } else {
+            RemoteString::Truncated(units)
+        })
+    }
+
+    /// Read a NUL-terminated UTF-16 string, as done by
+    /// [`read_utf16_cstr`](Self::read_utf16_cstr), then decode it, replacing
+    /// invalid data with [`U+FFFD`](char::REPLACEMENT_CHARACTER).
+    ///
+    /// Whether the string was truncated to `max_units` code units is not
+    /// reported.
+    pub fn read_utf16_string(&mut self, address: u64, max_units: usize) -> Result<String> {
+        let units = self.read_utf16_cstr(address, max_units)?.into_inner();
+        Ok(String::from_utf16_lossy(&units))
+    }
+
+    /// Read data from the virtual memory of the target process at `address`,
+    /// up to the first NUL code unit of `unit_size` bytes, or up to
+    /// `max_units` code units, whichever comes first.
+    ///
+    /// Returns the data read without the NUL code unit, and whether that code
+    /// unit was found. Data is read page by page, to avoid reading beyond the
+    /// page containing the NUL code unit.
+    fn read_nul_terminated(
+        &self,
+        mut address: u64,
+        unit_size: usize,
+        max_units: usize,
+    ) -> Result<(Vec<u8>, bool)> {
+        let max_len = max_units.saturating_mul(unit_size);
+        let page_size = usize::try_from(self.page_size)?;
+        let mut buffer =
+            ScratchBuffer::new(self.buffer_pool.as_ref(), cmp::min(page_size, max_len));
+        let mut data = Vec::new();
+
+        while data.len() < max_len {
+            let page_remaining = align_down(address, self.page_size)
+                .checked_add(self.page_size)
+                .map_or((u64::MAX - address).saturating_add(1), |next_page| {
+                    next_page - address
+                });
+            let size = cmp::min(page_remaining, (max_len - data.len()) as u64) as usize;
+
+            self.transfer_all_at(
+                process_vm_readv,
+                address,
+                buffer.as_mut_ptr(),
+                size,
+                io::ErrorKind::UnexpectedEof,
+            )?;
+            let chunk = &buffer[..size];
+
+            // Complete the code unit straddling the previous chunk, if any.
+            let partial_len = data.len() % unit_size;
+            let straddling_len = cmp::min((unit_size - partial_len) % unit_size, size);
+            let (straddling, rest) = chunk.split_at(straddling_len);
+            if partial_len != 0
+                && partial_len + straddling_len == unit_size
+                && data[data.len() - partial_len..]
+                    .iter()
+                    .chain(straddling)
+                    .all(|&byte| byte == 0)
+            {
+                data.truncate(data.len() - partial_len);
+                return Ok((data, true));
+            }
+
+            let nul_index = rest
+                .chunks_exact(unit_size)
+                .position(|unit| unit.iter().all(|&byte| byte == 0));
+            if let Some(index) = nul_index {
+                data.extend_from_slice(&chunk[..straddling_len + index * unit_size]);
+                return Ok((data, true));
+            }
+            data.extend_from_slice(chunk);
+
+            address = match address.checked_add(size as u64) {
+                Some(address) => address,
+                None if data.len() == max_len => break,
+                None => {
+                    // End of the address space, before the NUL code unit.
+                    return Err(Error::from_io3(
+                        io::ErrorKind::UnexpectedEof.into(),
+                        "process_vm_readv",
+                        self.process_id,
+                    ));
+                }
+            };
+        }
+        Ok((data, false))
+    }
+
+    /// Read a pointer from the virtual memory of the target process at `address`.
+    ///
+    /// The pointer is decoded according to the configured
+    /// [pointer width](Self::set_pointer_width) and
+    /// [byte order](Self::set_endianness), then zero-extended to 64 bits.
+    /// The current address is neither used nor modified.
+    pub fn read_pointer(&mut self, address: u64) -> Result<u64> {
+        let bytes = self.read_small::<8>(address, self.pointer_width.size())?;
+        Ok(self.endianness.decode_u64(&bytes))
+    }
+
+    /// Read `count` consecutive pointers from the virtual memory of the target
+    /// process at `address`.
+    ///
+    /// Pointers are decoded as done by [`read_pointer`](Self::read_pointer).
+    /// The current address is neither used nor modified.
+    pub fn read_pointers(&mut self, address: u64, count: usize) -> Result<Vec<u64>> {
+        let pointer_size = self.pointer_width.size();
+        let size = count.checked_mul(pointer_size).ok_or_else(|| {
+            Error::from_io3(
+                io::ErrorKind::InvalidInput.into(),
+                "process_vm_io::ProcessVirtualMemoryIO::read_pointers",
+                self.process_id,
+            )
+        })?;
+
+        let mut bytes = vec![0_u8; size];
+        self.transfer_all_at(
+            process_vm_readv,
+            address,
+            bytes.as_mut_ptr(),
+            size,
+            io::ErrorKind::UnexpectedEof,
+        )?;
+
+        Ok(bytes
+            .chunks_exact(pointer_size)
+            .map(|pointer| self.endianness.decode_u64(pointer))
+            .collect())
+    }
+
+    /// Follow a linked list stored in the virtual memory of the target process,
+    /// and return the addresses of its nodes, starting with `head`.
+    ///
+    /// The address of the node following a node is the pointer stored at
+    /// `next_offset` bytes from the start of that node, decoded as done by
+    /// [`read_pointer`](Self::read_pointer). The walk stops at a null pointer,
+    /// at a node that was already visited (i.e., a cycle), or after
+    /// `max_nodes` nodes. If a pointer cannot be read, then an error is
+    /// returned.
+    /// The current address is neither used nor modified.
+    pub fn walk_list(
+        &mut self,
+        head: u64,
+        next_offset: usize,
+        max_nodes: usize,
+    ) -> Result<Vec<u64>> {
+        let mut visited = HashSet::new();
+        let mut result = Vec::new();
+        let mut node = head;
+
+        while node != 0 && result.len() < max_nodes && visited.insert(node) {
+            result.push(node);
+
+            let next_address = node.checked_add(next_offset as u64).ok_or_else(|| {
+                Error::from_io3(
+                    io::ErrorKind::InvalidInput.into(),
+                    "process_vm_io::ProcessVirtualMemoryIO::walk_list",
+                    self.process_id,
+                )
+            })?;
+            node = self.read_pointer(next_address)?;
+        }
+        Ok(result)
+    }
+
+    /// Read a naturally aligned 64-bit word from the virtual memory of the
+    /// target process at `address`, decoded according to the configured
+    /// [byte order](Self::set_endianness).
+    ///
+    /// If `address` is not a multiple of 8, then an error of kind
+    /// [`InvalidInput`](io::ErrorKind::InvalidInput) is returned.
+    /// The current address is neither used nor modified.
+    ///
+    /// # Torn reads
+    ///
+    /// The system copies data without synchronizing with the target process.
+    /// If the target process is running and updates the word concurrently,
+    /// then the returned value might mix bytes of the old and new values.
+    /// An aligned word never straddles a page boundary, which makes this
+    /// unlikely, but not impossible. Use
+    /// [`read_word_consistent`](Self::read_word_consistent) to rule it out.
+    pub fn read_word_aligned(&mut self, address: u64) -> Result<u64> {
+        if !address.is_multiple_of(8) {
+            return Err(Error::from_io3(
+                io::ErrorKind::InvalidInput.into(),
+                "process_vm_io::ProcessVirtualMemoryIO::read_word_aligned",
+                self.process_id,
+            ));
+        }
+
+        let mut bytes = [0_u8; 8];
+        self.transfer_all_at(
+            process_vm_readv,
+            address,
+            bytes.as_mut_ptr(),
+            bytes.len(),
+            io::ErrorKind::UnexpectedEof,
+        )?;
+        Ok(self.endianness.decode_u64(&bytes))
+    }
+
+    /// Read a naturally aligned 64-bit word from the virtual memory of the
+    /// target process at `address`, while the target process is stopped.
+    ///
+    /// This behaves like [`read_word_aligned`](Self::read_word_aligned), but
+    /// [freezes](Self::freeze) the target process for the duration of the
+    /// read, so that the returned value cannot be torn by a concurrent update.
+    ///
+    /// Nothing is read if the target process cannot be frozen, in which case
+    /// the error returned by [`freeze`](Self::freeze) is returned. In
+    /// particular, if the target process is the current process, or one of
+    /// its threads, then an error of kind
+    /// [`InvalidInput`](io::ErrorKind::InvalidInput) is returned, and if the
+    /// target process does not stop in time, then an error of kind
+    /// [`TimedOut`](io::ErrorKind::TimedOut) is returned.
+    pub fn read_word_consistent(&mut self, address: u64) -> Result<u64> {
+        let _guard = self.freeze()?;
+        self.read_word_aligned(address)
+    }
+
+    /// Read a thread-local value of type `T` from the virtual memory of the
+    /// target process, at `offset` bytes from the thread-local storage (TLS)
+    /// base address `tls_base` of some thread of the target process.
+    ///
+    /// The TLS base address of a thread is architecture-specific. For example,
+    /// on x86-64, it is the value of the `fs_base` register, which can be
+    /// obtained by `ptrace(PTRACE_GETREGS, ...)` on the stopped thread.
+    /// The current address is neither used nor modified.
+    #[cfg(feature = "bytemuck")]
+    pub fn read_tls<T: bytemuck::AnyBitPattern>(
+        &mut self,
+        tls_base: u64,
+        offset: i64,
+    ) -> Result<T> {
+        let address = tls_base.checked_add_signed(offset).ok_or_else(|| {
+            Error::from_io3(
+                io::ErrorKind::InvalidInput.into(),
+                "process_vm_io::ProcessVirtualMemoryIO::read_tls",
+                self.process_id,
+            )
+        })?;
+
+        self.read_pod(address)
+    }
+
+    /// Read a value of type `T` from the virtual memory of the target process
+    /// at `address`.
+    ///
+    /// The value is read entirely, repeating the transfer as needed. If fewer
+    /// than `size_of::<T>()` bytes can be read, then an error is returned,
+    /// never a partially read value.
+    /// The current address is neither used nor modified.
+    #[cfg(feature = "bytemuck")]
+    pub fn read_pod<T: bytemuck::AnyBitPattern>(&mut self, address: u64) -> Result<T> {
+        use std::mem::MaybeUninit;
+
+        let mut value = MaybeUninit::<T>::zeroed();
+        self.transfer_all_at(
+            process_vm_readv,
+            address,
+            value.as_mut_ptr().cast(),
+            size_of::<T>(),
+            io::ErrorKind::UnexpectedEof,
+        )?;
+
+        // Safety: `T` is valid for any bit pattern.
+        Ok(unsafe { value.assume_init() })
+    }
+
+    /// Write `value` to the virtual memory of the target process at `address`.
+    ///
+    /// The value is written entirely, repeating the transfer as needed, as done
+    /// by [`write_all_at`](Self::write_all_at).
+    /// The current address is neither used nor modified.
+    #[cfg(feature = "bytemuck")]
+    pub fn write_pod<T: bytemuck::NoUninit>(&mut self, address: u64, value: &T) -> Result<()> {
+        self.write_all_at(address, bytemuck::bytes_of(value))
+    }
+
+    /// Write `new` to the virtual memory of the target process at `address`,
+    /// only if the data currently at `address` is equal to `expected`.
+    ///
+    /// Returns `true` if `new` was written, and `false` if the current data
+    /// differs from `expected`, in which case nothing is written. The write is
+    /// checked as configured by
+    /// [`set_check_writability`](Self::set_check_writability).
+    /// The current address is neither used nor modified.
+    ///
+    /// # Races
+    ///
+    /// The comparison and the write are **not** atomic. If the target process
+    /// is running, it may modify the data between the comparison and the write.
+    /// Consider stopping the target process before calling this method.
+    pub fn write_if_equal(&mut self, address: u64, expected: &[u8], new: &[u8]) -> Result<bool> {
+        let mut current = vec![0_u8; expected.len()];
+        self.transfer_all_at(
+            process_vm_readv,
+            address,
+            current.as_mut_ptr(),
+            current.len(),
+            io::ErrorKind::UnexpectedEof,
+        )?;
+
+        if current != expected {
+            return Ok(false);
+        }
+
+        self.check_writable(address, new.len())?;
+        self.transfer_all_at(
+            process_vm_writev,
+            address,
+            new.as_ptr() as *mut u8,
+            new.len(),
+            io::ErrorKind::WriteZero,
+        )?;
+        Ok(true)
+    }
+
+    /// Transform in place the data stored in the given address `range` of the
+    /// virtual memory of the target process, e.g., to patch or byte-swap it.
+    ///
+    /// The data is read chunk by chunk, chunks being aligned on page
+    /// boundaries. Each chunk is passed to `f`, along with its address, then
+    /// the chunk modified by `f` is written back. Returns the number of bytes
+    /// transformed and written back.
+    ///
+    /// Transforming stops early, without failing, at the first address that
+    /// cannot be read or written, after writing back the data read before that
+    /// address. Other errors are returned.
+    ///
+    /// Each chunk is checked as configured by
+    /// [`set_check_writability`](Self::set_check_writability) before being
+    /// read and transformed. If the first chunk is rejected, then an error of kind
+    /// [`ErrorKind::NotWritable`] is returned. If a later chunk is rejected,
+    /// then transforming stops there, without failing, like at an address that
+    /// cannot be written.
+    /// The current address is neither used nor modified.
+    ///
+    /// # Races
+    ///
+    /// Reading and writing back a chunk are **not** atomic. If the target
+    /// process is running, it may modify the data of a chunk between reading
+    /// and writing it back, and these modifications are then lost. Consider
+    /// stopping the target process before calling this method.
+    pub fn transform_region<F: FnMut(u64, &mut [u8])>(
+        &mut self,
+        range: Range<u64>,
+        mut f: F,
+    ) -> Result<u64> {
+        let (mut address, size) = self.extent_of(
+            range.clone(),
+            "process_vm_io::ProcessVirtualMemoryIO::transform_region",
+        )?;
+
+        let recommended_size = u64::try_from(self.recommended_buf_size()?)?;
+        let chunk_capacity = cmp::max(self.page_size, align_down(recommended_size, self.page_size));
+        let mut buffer = vec![0_u8; usize::try_from(cmp::min(chunk_capacity, size))?];
+
+        let mut total = 0_u64;
+        while address < range.end {
+            let chunk_end = cmp::min(
+                range.end,
+                align_down(address, self.page_size).saturating_add(chunk_capacity),
+            );
+            let chunk = &mut buffer[..(chunk_end - address) as usize];
+
+            match self.check_writable(address, chunk.len()) {
+                Err(err) if total != 0 && matches!(err.kind(), ErrorKind::NotWritable { .. }) => {
+                    break
+                }
+                result => result?,
+            }
+
+            let read_count = match self.read_at_most(address, chunk) {
+                Ok(count) => count,
+                Err(err) if err.os_error_code() == Some(libc::EFAULT) => break,
+                Err(err) => return Err(err),
+            };
+            if read_count == 0 {
+                break;
+            }
+
+            let data = &mut chunk[..read_count];
+            f(address, data);
+
+            let write_count = match self.transfer_at_most(
+                process_vm_writev,
+                address,
+                data.as_mut_ptr(),
+                data.len(),
+            ) {
+                Ok(count) => count,
+                Err(err) if err.os_error_code() == Some(libc::EFAULT) => break,
+                Err(err) => return Err(err),
+            };
+
+            total += write_count as u64;
+            if write_count < chunk.len() {
+                break;
+            }
+            address += write_count as u64;
+        }
+        Ok(total)
+    }
+
+    /// Compute how a data transfer over the given address `range` would be split
+    /// into remote `iovec`s, without transferring any data.
+    ///
+    /// The plan describes the remote `iovec`s of one system call, as issued by
+    /// e.g. [`read_at`](Self::read_at), with the current configuration.
+    /// This is useful to understand why a transfer over a large address range
+    /// transferred only a prefix of that range.
+    pub fn plan_transfer(&self, range: Range<u64>) -> Result<TransferPlan> {
+        let (start_address, size) = self.extent_of(
+            range,
+            "process_vm_io::ProcessVirtualMemoryIO::plan_transfer",
+        )?;
+
+        if size == 0 {
+            return Ok(TransferPlan {
+                segments: Vec::new(),
+                size_of_not_covered_suffix: 0,
+            });
+        }
+
+        let byte_count = self.limit_bytes_per_call(size);
+        let (remote_io_vectors, size_of_not_covered_suffix) =
+            self.remote_io_vectors(start_address, byte_count)?;
+        let size_of_not_covered_suffix = (size - byte_count) + size_of_not_covered_suffix;
+
+        let segments = remote_io_vectors
+            .iter()
+            .map(|io_vector| (io_vector.iov_base as u64, io_vector.iov_len))
+            .collect();
+
+        Ok(TransferPlan {
+            segments,
+            size_of_not_covered_suffix,
+        })
+    }
+
+    /// Hint the system that the target process will soon have its virtual
+    /// memory accessed over the given address `range`, so that the pages in
+    /// that range can be brought into memory ahead of time.
+    ///
+    /// This relies on the `process_madvise()` system call with the advice
+    /// `MADV_WILLNEED`. Kernels that do not support that system call cause
+    /// an error of kind [`Unsupported`](io::ErrorKind::Unsupported) to be
+    /// returned. Applying this advice also requires the `CAP_SYS_NICE`
+    /// capability.
+    ///
+    /// The address range is extended to page boundaries.
+    pub fn prefetch(&self, range: Range<u64>) -> Result<()> {
+        let (start_address, size) =
+            self.extent_of(range, "process_vm_io::ProcessVirtualMemoryIO::prefetch")?;
+
+        if size == 0 {
+            return Ok(());
+        }
+
+        let min_page_size = *MIN_SYSTEM_PAGE_SIZE;
+        let mut address = align_down(start_address, min_page_size);
+        let end_address = align_down(
+            (start_address + size).saturating_add(min_page_size - 1),
+            min_page_size,
+        );
+
+        let process_fd = self.process_fd()?;
+        let remote_io_vectors_count: c_ulong = 1;
+        let flags: c_uint = 0;
+        while address < end_address {
+            let remote_io_vector = libc::iovec {
+                iov_base: usize::try_from(address)? as *mut c_void,
+                iov_len: usize::try_from(end_address - address)?,
+            };
+
+            let advised_bytes_count = unsafe {
+                libc::syscall(
+                    libc::SYS_process_madvise,
+                    process_fd.as_raw_fd(),
+                    ptr::addr_of!(remote_io_vector),
+                    remote_io_vectors_count,
+                    libc::MADV_WILLNEED,
+                    flags,
+                )
+            };
+
+            match advised_bytes_count {
+                -1 => {
+                    return Err(Error::from_io3(
+                        io::Error::last_os_error(),
+                        "process_madvise",
+                        self.process_id,
+                    ))
+                }
+                0 => break,
+                count => address += count as u64,
+            }
+        }
+        Ok(())
+    }
+
+    /// Return a file descriptor referring to the target process, i.e., a
+    /// `pidfd`, opened by the first call to this method.
+    ///
+    /// The file descriptor becomes readable when the target process exits,
+    /// so it can be registered in an event loop, e.g., based on `epoll()`,
+    /// to be notified of the termination of the target process. It keeps
+    /// referring to the target process even if its process identifier is
+    /// reused afterwards. See also [`exit_readiness`](Self::exit_readiness).
+    pub fn process_fd(&self) -> Result<BorrowedFd<'_>> {
+        if let Some(fd) = self.process_fd.get() {
+            return Ok(fd.as_fd());
+        }
+
+        let fd = self.open_process_fd()?;
+        Ok(self.process_fd.get_or_init(|| fd).as_fd())
+    }
+
+    /// Determine, without blocking, whether the target process exited, by
+    /// polling the file descriptor returned by
+    /// [`process_fd`](Self::process_fd) for readability.
+    ///
+    /// Unlike [`is_alive`](Self::is_alive), this does not involve the `/proc`
+    /// file system, and cannot be confused by the reuse of the process
+    /// identifier of the target process once the file descriptor is opened.
+    pub fn exit_readiness(&self) -> Result<bool> {
+        let mut poll_fd = libc::pollfd {
+            fd: self.process_fd()?.as_raw_fd(),
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        match unsafe { libc::poll(&mut poll_fd, 1, 0) } {
+            -1 => Err(Error::from_io3(
+                io::Error::last_os_error(),
+                "poll",
+                self.process_id,
+            )),
+            0 => Ok(false),
+            _ => Ok(poll_fd.revents & libc::POLLIN != 0),
+        }
+    }
+
+    /// Open a file descriptor referring to the target process.
+    fn open_process_fd(&self) -> Result<OwnedFd> {
+        let flags: c_uint = 0;
+        let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, self.process_id, flags) };
+        if fd == -1 {
+            return Err(Error::from_io3(
+                io::Error::last_os_error(),
+                "pidfd_open",
+                self.process_id,
+            ));
+        }
+        Ok(unsafe { OwnedFd::from_raw_fd(fd as RawFd) })
+    }
+
+    /// Return the memory regions currently mapped in the virtual address space
+    /// of the target process, sorted by address.
+    ///
+    /// The result is a snapshot. The target process may change its mappings at
+    /// any time.
+    pub fn memory_maps(&self) -> Result<Vec<MemoryRegion>> {
+        procfs::memory_maps(self.process_id)
+    }
+
+    /// Read the memory regions currently mapped in the virtual address space
+    /// of the target process, and cache them for later queries, such as
+    /// [`region_containing`](Self::region_containing).
+    ///
+    /// Call this again after the target process changes its mappings.
+    pub fn refresh_maps(&mut self) -> Result<()> {
+        self.maps_cache = Some(self.memory_maps()?);
+        Ok(())
+    }
+
+    /// Return the cached memory region of the target process that contains
+    /// `address`, if any.
+    ///
+    /// Memory regions are read and cached on first use, then reused until
+    /// [`refresh_maps`](Self::refresh_maps) is called. The cached regions are
+    /// looked up by binary search, so repeated calls are cheap.
+    ///
+    /// The cached regions become stale when the target process changes its
+    /// mappings, e.g., by `mmap()` or `munmap()`, possibly concurrently with
+    /// this call. The returned region then does not prove that `address` is
+    /// still mapped, nor does `None` prove that it is not.
+    pub fn region_containing(&mut self, address: u64) -> Result<Option<&MemoryRegion>> {
+        if self.maps_cache.is_none() {
+            self.refresh_maps()?;
+        }
+
+        let regions = self.maps_cache.as_deref().unwrap_or_default();
+        let index = regions.partition_point(|region| region.end <= address);
+        Ok(regions.get(index).filter(|region| region.start <= address))
+    }
+
+    /// Check that `len` bytes could be written to the virtual memory of the
+    /// target process at `address`, without writing anything.
+    ///
+    /// The address range is checked against the cached memory regions, as
+    /// maintained by [`region_containing`](Self::region_containing).
+    /// If some part of the range is not mapped, then an error with the code
+    /// `EFAULT` is returned, which is what writing would report. If some part
+    /// of the range is mapped without write permission, then an error of kind
+    /// [`ErrorKind::NotWritable`] is returned, with the first address of that
+    /// part, as reported by writes when
+    /// [`set_check_writability`](Self::set_check_writability) is enabled.
+    pub fn validate_write(&mut self, address: u64, len: usize) -> Result<()> {
+        const OPERATION: &str = "process_vm_io::ProcessVirtualMemoryIO::validate_write";
+
+        let end = address.checked_add(len as u64).ok_or_else(|| {
+            Error::from_io3(
+                io::ErrorKind::InvalidInput.into(),
+                OPERATION,
+                self.process_id,
+            )
+        })?;
+
+        let mut address = address;
+        while address < end {
+            let (region_end, writable) = match self.region_containing(address)? {
+                Some(region) => (region.end, region.perms.write),
+                None => {
+                    return Err(Error::from_io3(
+                        io::Error::from_raw_os_error(libc::EFAULT),
+                        OPERATION,
+                        self.process_id,
+                    ))
+                }
+            };
+
+            if !writable {
+                return Err(ErrorKind::NotWritable {
+                    address,
+                    process_id: self.process_id,
+                }
+                .into());
+            }
+            address = region_end;
+        }
+        Ok(())
+    }
+
+    /// Return the aggregate memory usage of the target process.
+    ///
+    /// This is cheaper than inspecting every memory region of the process.
+    /// On kernels that do not provide `/proc/[pid]/smaps_rollup`, the usage
+    /// of every memory region is summed instead.
+    pub fn memory_summary(&self) -> Result<MemorySummary> {
+        procfs::memory_summary(self.process_id)
+    }
+
+    /// Convert an address `range` as done by [`range_to_extent`], reporting
+    /// errors as failures of `operation` on the target process.
+    fn extent_of(&self, range: Range<u64>, operation: &'static str) -> Result<(u64, u64)> {
+        range_to_extent(range).map_err(|_err| {
+            Error::from_io3(
+                io::ErrorKind::InvalidInput.into(),
+                operation,
+                self.process_id,
+            )
+        })
+    }
+
+    /// Return the readable memory regions of the target process that intersect
+    /// the given address `range`, clipped to `range`.
+    ///
+    /// Pseudo-regions in [`UNREADABLE_PSEUDO_REGIONS`] are omitted.
+    fn readable_regions_in(
+        &self,
+        range: Range<u64>,
+        operation: &'static str,
+    ) -> Result<Vec<MemoryRegion>> {
+        self.extent_of(range.clone(), operation)?;
+
+        let mut result = self.memory_maps()?;
+        result.retain(|region| {
+            region.perms.read
+                && region.end > range.start
+                && region.start < range.end
+                && !region
+                    .pathname
+                    .as_deref()
+                    .is_some_and(|pathname| UNREADABLE_PSEUDO_REGIONS.contains(&pathname))
+        });
+
+        for region in &mut result {
+            region.start = cmp::max(region.start, range.start);
+            region.end = cmp::min(region.end, range.end);
+        }
+        Ok(result)
+    }
+
+    /// Return an iterator over the readable memory regions of the target
+    /// process, each accompanied by a reader of the data stored in it.
+    ///
+    /// Memory regions that are readable according to their permissions, but
+    /// from which nothing can actually be read, such as `[vvar]` and
+    /// `[vsyscall]`, are skipped.
+    /// The current address is neither used nor modified.
+    pub fn readable_regions(
+        &mut self,
+    ) -> Result<impl Iterator<Item = (MemoryRegion, BoundedReader<'_>)> + '_> {
+        self.readable_regions_within(
+            0..u64::MAX,
+            "process_vm_io::ProcessVirtualMemoryIO::readable_regions",
+        )
+    }
+
+    /// Return an iterator over the memory regions of the target process that
+    /// [`readable_regions`](Self::readable_regions) would yield, clipped to
+    /// the given address `range`.
+    fn readable_regions_within(
+        &mut self,
+        range: Range<u64>,
+        operation: &'static str,
+    ) -> Result<impl Iterator<Item = (MemoryRegion, BoundedReader<'_>)> + '_> {
+        let regions = self.readable_regions_in(range, operation)?;
+
+        let io = &*self;
+        Ok(regions.into_iter().filter_map(move |region| {
+            let mut probe = [0_u8; 1];
+            let local_io_vector = libc::iovec {
+                iov_base: probe.as_mut_ptr() as *mut c_void,
+                iov_len: probe.len(),
+            };
+            io.io_vectored_at(process_vm_readv, region.start, &[local_io_vector], 1)
+                .is_ok_and(|count| count == 1)
+                .then(|| {
+                    let reader = BoundedReader::new(io, region.start..region.end);
+                    (region, reader)
+                })
+        }))
+    }
+
+    /// Copy the data stored in `region` of the virtual memory of the target
+    /// process to `out`, and return the number of bytes copied.
+    ///
+    /// The current address is set to the start of `region`, then advanced as
+    /// data is copied, transferring data in chunks of a bounded size, and
+    /// repeating transfers that read less than requested. On success, the
+    /// current address is therefore the end of `region`.
+    ///
+    /// Only the parts of `region` that
+    /// [`readable_regions`](Self::readable_regions) yields are read. At the
+    /// first address of `region` outside of them, e.g., in a guard page, an
+    /// error with the system error code `EFAULT` is returned, and the current
+    /// address is that address. If an address cannot be read nonetheless,
+    /// e.g., because the region was unmapped in the mean time, then the error
+    /// reported by the system is returned, and the current address is that
+    /// address. In both cases, the data before it has already been copied to
+    /// `out`, and its size is the difference between the current address and
+    /// the start of `region`. Errors writing to `out` are returned as well.
+    ///
+    /// To dump all readable memory regions without aborting at the first one
+    /// that faults, collect them first, then handle the error of each dump,
+    /// e.g., by recording the region as partially dumped and going on with
+    /// the next one:
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let process_id = std::process::id();
+    /// # let mut out = std::io::sink();
+    /// use process_vm_io::ProcessVirtualMemoryIO;
+    ///
+    /// let mut process_io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }?;
+    /// let regions: Vec<_> = process_io
+    ///     .readable_regions()?
+    ///     .map(|(region, _reader)| region)
+    ///     .collect();
+    ///
+    /// for region in &regions {
+    ///     if let Err(err) = process_io.dump_region(region, &mut out) {
+    ///         let copied = process_io.address().unwrap_or(region.end) - region.start;
+    ///         eprintln!("{region:?}: copied only {copied} bytes: {err}");
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn dump_region<W: Write + ?Sized>(
+        &mut self,
+        region: &MemoryRegion,
+        out: &mut W,
+    ) -> Result<u64> {
+        const OPERATION: &str = "process_vm_io::ProcessVirtualMemoryIO::dump_region";
+        const CHUNK_PAGES: u64 = 256;
+
+        let size = region.end.saturating_sub(region.start);
+        let mut address = region.start;
+        self.address = Some(address);
+        if size == 0 {
+            return Ok(0);
+        }
+
+        let readable: Vec<MemoryRegion> = self
+            .readable_regions_within(region.start..region.end, OPERATION)?
+            .map(|(readable, _reader)| readable)
+            .collect();
+
+        let chunk_size = cmp::min(size, self.page_size * CHUNK_PAGES);
+        let mut buffer =
+            ScratchBuffer::new(self.buffer_pool.as_ref(), usize::try_from(chunk_size)?);
+
+        for readable in &readable {
+            if readable.start != address {
+                break;
+            }
+            while address < readable.end {
+                let size = cmp::min(readable.end - address, chunk_size) as usize;
+                let count = self.read_at_most(address, &mut buffer[..size])?;
+                if count == 0 {
+                    return Err(Error::from_io3(
+                        io::ErrorKind::UnexpectedEof.into(),
+                        OPERATION,
+                        self.process_id,
+                    ));
+                }
+                out.write_all(&buffer[..count])
+                    .map_err(|err| Error::from_io2(err, OPERATION))?;
+
+                address += count as u64;
+                self.address = Some(address);
+            }
+        }
+
+        if address < region.end {
+            return Err(Error::from_io3(
+                io::Error::from_raw_os_error(libc::EFAULT),
+                OPERATION,
+                self.process_id,
+            ));
+        }
+        Ok(size)
+    }
+
+    /// Read the readable memory regions of the target process that intersect
+    /// the given address `range`.
+    ///
+    /// Each returned region is clipped to `range`, and is accompanied by the
+    /// data read from it. If a region cannot be read completely (e.g., due to
+    /// a guard page), then its data is truncated at the first address that
+    /// cannot be read, and regions from which nothing can be read are omitted.
+    pub fn read_regions_in(&mut self, range: Range<u64>) -> Result<Vec<(MemoryRegion, Vec<u8>)>> {
+        let regions = self.readable_regions_in(
+            range,
+            "process_vm_io::ProcessVirtualMemoryIO::read_regions_in",
+        )?;
+
+        let mut result = Vec::with_capacity(regions.len());
+        for region in regions {
+            let mut data = vec![0_u8; usize::try_from(region.end - region.start)?];
+            let count = self.read_until_fault(region.start, &mut data)?;
+            if count != 0 {
+                data.truncate(count);
+                result.push((region, data));
+            }
+        }
+        Ok(result)
+    }
+
+    /// Read the virtual dynamic shared object (vDSO) mapped by the kernel into
+    /// the target process, i.e., the contents of its `[vdso]` memory region.
+    ///
+    /// The vDSO is an ELF image that the target process calls into, e.g., for
+    /// `clock_gettime()`, so it can be dumped and parsed like any library.
+    /// Unlike the neighboring `[vvar]` region, which holds kernel data pages
+    /// that cannot be read through `process_vm_readv()`, and the legacy
+    /// `[vsyscall]` page, which is not backed by the address space of the
+    /// process, `[vdso]` is readable. Those pseudo-regions are skipped by the
+    /// methods reading all readable regions, such as
+    /// [`read_regions_in`](Self::read_regions_in), so that they do not cause
+    /// spurious faults.
+    ///
+    /// If the target process has no vDSO, then an error of kind
+    /// [`NotFound`](io::ErrorKind::NotFound) is returned.
+    /// The current address is neither used nor modified.
+    pub fn read_vdso(&mut self) -> Result<Vec<u8>> {
+        let region = self
+            .memory_maps()?
+            .into_iter()
+            .find(|region| region.pathname.as_deref() == Some("[vdso]"))
+            .ok_or_else(|| {
+                Error::from_io3(
+                    io::ErrorKind::NotFound.into(),
+                    "process_vm_io::ProcessVirtualMemoryIO::read_vdso",
+                    self.process_id,
+                )
+            })?;
+
+        let size = usize::try_from(region.end - region.start)?;
+        let mut result = vec![0_u8; size];
+        self.transfer_all_at(
+            process_vm_readv,
+            region.start,
+            result.as_mut_ptr(),
+            size,
+            io::ErrorKind::UnexpectedEof,
+        )?;
+        Ok(result)
+    }
+
+    /// Find every address in the given `range` of the virtual memory of the
+    /// target process where a value equal to `value` is stored.
+    ///
+    /// Only readable memory regions are scanned, and holes between them are
+    /// skipped. Values straddling adjacent readable memory regions, e.g., parts
+    /// of a heap with different permissions, are found as well. If `aligned`
+    /// is `true`, then only addresses that are multiples
+    /// of the alignment of `T` are considered; otherwise, every address is.
+    /// Data is read in chunks of [`recommended_buf_size`] bytes, so that large
+    /// ranges are not buffered entirely. Returned addresses are sorted.
+    /// The current address is neither used nor modified.
+    ///
+    /// [`recommended_buf_size`]: Self::recommended_buf_size
+    #[cfg(feature = "bytemuck")]
+    pub fn scan_value<T: bytemuck::Pod + PartialEq>(
+        &mut self,
+        range: Range<u64>,
+        value: &T,
+        aligned: bool,
+    ) -> Result<Vec<u64>> {
+        const OPERATION: &str = "process_vm_io::ProcessVirtualMemoryIO::scan_value";
+
+        let value_size = size_of::<T>() as u64;
+        if value_size == 0 {
+            return Err(Error::from_io3(
+                io::ErrorKind::InvalidInput.into(),
+                OPERATION,
+                self.process_id,
+            ));
+        }
+
+        let step = if aligned { align_of::<T>() as u64 } else { 1 };
+        let chunk_size = u64::try_from(self.recommended_buf_size()?)?;
+        let regions = self.readable_regions_in(range, OPERATION)?;
+
+        let mut result = Vec::new();
+
+        // `buffer` holds the data read from `buffer_start` to `read_address`,
+        // and `address` is the address of the next candidate value. They are
+        // kept across adjacent regions, and reset at holes.
+        let mut buffer = Vec::new();
+        let mut buffer_start = 0;
+        let mut read_address = 0;
+        let mut address = 0;
+        let mut contiguous = false;
+        for region in regions {
+            if !contiguous || region.start != read_address {
+                let Some(start) = region.start.checked_next_multiple_of(step) else {
+                    contiguous = false;
+                    continue;
+                };
+                buffer.clear();
+                buffer_start = region.start;
+                read_address = region.start;
+                address = start;
+            }
+
+            while read_address < region.end {
+                let size = usize::try_from(cmp::min(chunk_size, region.end - read_address))?;
+                let old_len = buffer.len();
+                buffer.resize(old_len + size, 0);
+                let count = self.read_until_fault(read_address, &mut buffer[old_len..])?;
+                buffer.truncate(old_len + count);
+                read_address += count as u64;
+
+                while address - buffer_start + value_size <= buffer.len() as u64 {
+                    let offset = usize::try_from(address - buffer_start)?;
+                    let window = &buffer[offset..offset + value_size as usize];
+                    if bytemuck::pod_read_unaligned::<T>(window) == *value {
+                        result.push(address);
+                    }
+                    address = address.saturating_add(step);
+                }
+
+                // Keep only the data that candidate values might still need.
+                let consumed = cmp::min(address - buffer_start, buffer.len() as u64);
+                buffer.drain(..consumed as usize);
+                buffer_start += consumed;
+
+                if count == 0 {
+                    break;
+                }
+            }
+            contiguous = read_address == region.end;
+        }
+        Ok(result)
+    }
+
+    /// Keep the addresses among `candidates` where a value equal to `value` is
+    /// currently stored in the virtual memory of the target process.
+    ///
+    /// This refines the result of [`scan_value`](Self::scan_value) after the
+    /// target process changed the values being searched for. Candidates are
+    /// read in batches by [`read_scattered`](Self::read_scattered), one system
+    /// call per batch. Candidates that cannot be read anymore are dropped.
+    /// The order of candidates is preserved.
+    /// The current address is neither used nor modified.
+    #[cfg(feature = "bytemuck")]
+    pub fn filter_addresses<T: bytemuck::Pod + PartialEq>(
+        &mut self,
+        candidates: &[u64],
+        value: &T,
+    ) -> Result<Vec<u64>> {
+        let value_size = size_of::<T>();
+        if value_size == 0 {
+            return Err(Error::from_io3(
+                io::ErrorKind::InvalidInput.into(),
+                "process_vm_io::ProcessVirtualMemoryIO::filter_addresses",
+                self.process_id,
+            ));
+        }
+
+        let batch_len = cmp::min(self.max_iov_count(), candidates.len());
+        let mut buffer = vec![0_u8; batch_len * value_size];
+
+        let mut result = Vec::new();
+        let mut remaining = candidates;
+        while !remaining.is_empty() {
+            let batch = &remaining[..cmp::min(batch_len, remaining.len())];
+            let remote: Vec<(u64, usize)> =
+                batch.iter().map(|&address| (address, value_size)).collect();
+            let local = &mut [IoSliceMut::new(&mut buffer[..batch.len() * value_size])];
+            let read_count = match self.read_scattered(&remote, local) {
+                Ok(count) => count / value_size,
+                Err(err) if err.os_error_code() == Some(libc::EFAULT) => 0,
+                Err(err) => return Err(err),
+            };
+
+            for (address, bytes) in batch[..read_count]
+                .iter()
+                .zip(buffer.chunks_exact(value_size))
+            {
+                if bytemuck::pod_read_unaligned::<T>(bytes) == *value {
+                    result.push(*address);
+                }
+            }
+
+            // Skip the candidate that cannot be read, if any.
+            remaining = &remaining[cmp::min(read_count + 1, batch.len())..];
+        }
+        Ok(result)
+    }
+
+    /// Read `count` consecutive pointers from the virtual memory of the target
+    /// process at `table_address`, then read a value of type `T` at each of
+    /// the addresses they point to.
+    ///
+    /// Pointers are decoded as done by [`read_pointer`](Self::read_pointer).
+    /// Each value is read independently, so that an invalid pointer, e.g., a
+    /// null pointer, only fails its own entry of the returned vector. Values
+    /// are read in batches by [`read_scattered`](Self::read_scattered), one
+    /// system call per batch.
+    /// The current address is neither used nor modified.
+    #[cfg(feature = "bytemuck")]
+    pub fn read_and_deref<T: bytemuck::AnyBitPattern>(
+        &mut self,
+        table_address: u64,
+        count: usize,
+    ) -> Result<Vec<Result<T>>> {
+        let pointers = self.read_pointers(table_address, count)?;
+
+        let value_size = size_of::<T>();
+        if value_size == 0 {
+            return Ok(pointers.iter().map(|_| Ok(T::zeroed())).collect());
+        }
+
+        let batch_len = cmp::min(self.max_iov_count(), pointers.len());
+        let mut buffer = vec![0_u8; batch_len * value_size];
+
+        let mut result = Vec::with_capacity(pointers.len());
+        let mut remaining = pointers.as_slice();
+        while !remaining.is_empty() {
+            let batch = &remaining[..cmp::min(batch_len, remaining.len())];
+            let remote: Vec<(u64, usize)> =
+                batch.iter().map(|&address| (address, value_size)).collect();
+            let local = &mut [IoSliceMut::new(&mut buffer[..batch.len() * value_size])];
+            let read_count = match self.read_scattered(&remote, local) {
+                Ok(count) => count / value_size,
+                Err(err) if err.os_error_code() == Some(libc::EFAULT) => 0,
+                Err(err) => return Err(err),
+            };
+
+            result.extend(
+                buffer
+                    .chunks_exact(value_size)
+                    .take(read_count)
+                    .map(|bytes| Ok(bytemuck::pod_read_unaligned::<T>(bytes))),
+            );
+
+            // Report the pointer that cannot be dereferenced, if any.
+            if read_count < batch.len() {
+                result.push(Err(Error::from_io3(
+                    io::Error::from_raw_os_error(libc::EFAULT),
+                    system_call_name(process_vm_readv, self.backend()),
+                    self.process_id,
+                )));
+            }
+            remaining = &remaining[cmp::min(read_count + 1, batch.len())..];
+        }
+        Ok(result)
+    }
+
+    /// Read the readable parts of the given address `range` of the virtual
+    /// memory of the target process, packed into one buffer.
+    ///
+    /// Unlike [`dump_region_zero_filled`](Self::dump_region_zero_filled), the
+    /// unreadable parts of `range` take no space. The returned image records
+    /// where the data of each readable part comes from, so that offsets in the
+    /// packed buffer can be mapped back to addresses, e.g., after searching
+    /// the buffer.
+    pub fn read_all_into(&mut self, range: Range<u64>) -> Result<SparseImage> {
+        let mut result = SparseImage::default();
+        for (region, data) in self.read_regions_in(range)? {
+            let offset = result.data.len();
+            result.data.extend_from_slice(&data);
+            result
+                .segments
+                .push((region.start, offset..result.data.len()));
+        }
+        Ok(result)
+    }
+
+    /// Read the readable parts of the given address `range` of the virtual
+    /// memory of the target process, as done by
+    /// [`read_all_into`](Self::read_all_into), and hold that data for later
+    /// reads by [`read_cow`](Self::read_cow), replacing any data previously
+    /// held.
+    pub fn take_snapshot(&mut self, range: Range<u64>) -> Result<()> {
+        self.snapshot = Some(self.read_all_into(range)?);
+        Ok(())
+    }
+
+    /// Release the data held by [`take_snapshot`](Self::take_snapshot), if
+    /// any, and return it.
+    pub fn clear_snapshot(&mut self) -> Option<SparseImage> {
+        self.snapshot.take()
+    }
+
+    /// Read exactly `len` bytes from the virtual memory of the target process
+    /// at `address`, borrowing them from the data held by
+    /// [`take_snapshot`](Self::take_snapshot) when possible.
+    ///
+    /// If the snapshot holds all requested bytes, then they are returned
+    /// without copying. Otherwise, they are read from the target process.
+    /// Beware that borrowed data reflects the virtual memory of the target
+    /// process at the time of the snapshot, not its current state.
+    /// The current address is neither used nor modified.
+    pub fn read_cow(&mut self, address: u64, len: usize) -> Result<Cow<'_, [u8]>> {
+        if let Some(data) = self.snapshot.as_ref().and_then(|s| s.get(address, len)) {
+            return Ok(Cow::Borrowed(data));
+        }
+
+        let mut result = vec![0_u8; len];
+        self.transfer_all_at(
+            process_vm_readv,
+            address,
+            result.as_mut_ptr(),
+            len,
+            io::ErrorKind::UnexpectedEof,
+        )?;
+        Ok(Cow::Owned(result))
+    }
+
+    /// Return an iterator over the lines of text stored in the given address
+    /// `range` of the virtual memory of the target process.
+    ///
+    /// The address range is read one page at a time, as lines are requested.
+    /// Lines are terminated by `\n` or `\r\n`, which are not included in the
+    /// returned lines. Invalid UTF-8 sequences are replaced by
+    /// [`U+FFFD REPLACEMENT CHARACTER`](std::char::REPLACEMENT_CHARACTER).
+    /// If some part of `range` cannot be read, then the iterator returns an
+    /// error, then ends.
+    /// The current address is neither used nor modified.
+    pub fn lines(&mut self, range: Range<u64>) -> RemoteLines<'_> {
+        RemoteLines::new(self, range)
+    }
+
+    /// Read the data stored in the given address `range` of the virtual memory
+    /// of the target process, filling with zeros the parts of `range` that
+    /// cannot be read.
+    ///
+    /// The returned data is as long as `range`, so that the data stored at an
+    /// address `a` is at offset `a - range.start`. Beware that reading a
+    /// large and sparsely mapped address range allocates a large buffer,
+    /// mostly filled with zeros. Consider
+    /// [`read_regions_in`](Self::read_regions_in) for such address ranges.
+    pub fn dump_region_zero_filled(&mut self, range: Range<u64>) -> Result<Vec<u8>> {
+        const OPERATION: &str = "process_vm_io::ProcessVirtualMemoryIO::dump_region_zero_filled";
+
+        let (_start_address, size) = self.extent_of(range.clone(), OPERATION)?;
+        let regions = self.readable_regions_in(range.clone(), OPERATION)?;
+
+        let mut result = vec![0_u8; usize::try_from(size)?];
+        for region in regions {
+            let offset = usize::try_from(region.start - range.start)?;
+            let size = usize::try_from(region.end - region.start)?;
+            let data = &mut result[offset..offset + size];
+            let count = self.read_until_fault(region.start, data)?;
+            data[count..].fill(0);
+        }
+        Ok(result)
+    }
+}
+
+impl Seek for ProcessVirtualMemoryIO {
+    /// Seek to an address in the virtual memory address space of the associated
+    /// process.
+    ///
+    /// If the seek operation completed successfully, this method returns
+    /// the new position from the start of the stream.
+    ///
+    /// The target address need not be readable/writable or even mapped.
+    /// Seeking beyond the address space size puts the cursor after the end
+    /// of the address space. The returned position is then `u64::MAX`, as
+    /// when seeking to the address `u64::MAX`. Use
+    /// [`ProcessVirtualMemoryIO::address`] to tell them apart.
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.address = seek_address(self.address, pos)?;
+        Ok(self.address.unwrap_or(u64::MAX))
+    }
+}
+
+/// Create an object performing input/output of data from/to the virtual
+/// memory contents of the process identified by a process identifier, with an
+/// initial address of zero.
+///
+/// This conversion is a convenience that is not marked `unsafe`, but it is
+/// as hazardous as [`ProcessVirtualMemoryIO::new`]: writing to the virtual
+/// memory of a process may introduce memory unsafety in that process, and may
+/// lead to unexpected states in that process.
+///
+/// To keep this from affecting the currently running process, process
+/// identifiers referring to a process that shares its virtual memory are
+/// refused with an error of kind
+/// [`InvalidInput`](io::ErrorKind::InvalidInput). These are the identifier of
+/// the current process, the thread identifiers of its threads, which data
+/// transfers accept as well, and the identifiers of processes created by
+/// `clone()` with the `CLONE_VM` flag, as detected by `kcmp()`. If `kcmp()`
+/// fails, e.g., because the kernel does not provide it, then its error is
+/// returned, without creating the object.
+///
+/// These checks only hold when the conversion happens. If the target process
+/// terminates, and is reaped, then its identifier might be reused by a new
+/// thread of the current process, which data transfers would then target.
+/// Callers must rule this out, e.g., by being the parent of the target
+/// process, and not reaping it before dropping the returned object.
+impl TryFrom<u32> for ProcessVirtualMemoryIO {
+    type Error = Error;
+
+    fn try_from(process_id: u32) -> Result<Self> {
+        if shares_current_virtual_memory(process_id as libc::pid_t)? {
+            return Err(Error::from_io3(
+                io::ErrorKind::InvalidInput.into(),
+                "process_vm_io::ProcessVirtualMemoryIO::try_from",
+                process_id as libc::pid_t,
+            ));
+        }
+
+        unsafe { Self::new(process_id, 0) }
+    }
+}
+
+/// Create an object performing input/output of data from/to the virtual
+/// memory contents of the process described by a path of the form
+/// `/proc/[pid]`, with an initial address of zero.
+///
+/// This behaves like the conversion from a process identifier, including its
+/// checks and hazards.
+impl TryFrom<&Path> for ProcessVirtualMemoryIO {
+    type Error = Error;
+
+    fn try_from(path: &Path) -> Result<Self> {
+        let process_id = path
+            .strip_prefix("/proc")
+            .ok()
+            .and_then(Path::to_str)
+            .and_then(|process_id| process_id.parse::<u32>().ok())
+            .ok_or_else(|| {
+                Error::from_io2(
+                    io::ErrorKind::InvalidInput.into(),
+                    "process_vm_io::ProcessVirtualMemoryIO::try_from",
+                )
+            })?;
+
+        Self::try_from(process_id)
+    }
+}
+
+impl Read for ProcessVirtualMemoryIO {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.read_raw(buf).map_err(io::Error::from)
+    }
+
+    fn read_vectored(&mut self, bufs: &mut [IoSliceMut]) -> io::Result<usize> {
+        let bytes_to_read = bufs.iter().map(|buf| buf.len() as u64).sum();
+        if bytes_to_read == 0 {
+            // No system call for empty or all-empty buffers.
+            return Ok(0);
+        }
+
+        let local_io_vectors =
+            unsafe { slice::from_raw_parts(bufs.as_ptr() as *const _, bufs.len()) };
+
+        self.io_vectored(process_vm_readv, local_io_vectors, bytes_to_read)
+            .map_err(io::Error::from)
+    }
+}
+
+impl Write for ProcessVirtualMemoryIO {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.write_raw(buf).map_err(io::Error::from)
+    }
+
+    fn write_vectored(&mut self, bufs: &[IoSlice]) -> io::Result<usize> {
+        let bytes_to_write = bufs.iter().map(|buf| buf.len() as u64).sum();
+        if bytes_to_write == 0 {
+            // No system call for empty or all-empty buffers.
+            return Ok(0);
+        }
+
+        if let Some(address) = self.address {
+            self.check_writable(address, bytes_to_write as usize)?;
+        }
+
         let local_io_vectors =
             unsafe { slice::from_raw_parts(bufs.as_ptr() as *const _, bufs.len()) };
 
-        self.io_vectored(libc::process_vm_writev, local_io_vectors, bytes_to_write)
-            .map_err(|err| io::Error::new(io::ErrorKind::Other, err))
+        self.io_vectored(process_vm_writev, local_io_vectors, bytes_to_write)
+            .map_err(io::Error::from)
     }
 
     fn flush(&mut self) -> io::Result<()> {
@@ -0,0 +1,123 @@
+// Copyright (c) 2020-2024 MicroDoc Software GmbH.
+// See the "LICENSE.txt" file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license. This file may not be copied, modified,
+// or distributed except according to those terms.
+
+/*! Iteration over lines of text stored in the virtual memory of a process. */
+
+use std::cmp;
+use std::ffi::c_void;
+use std::ops::Range;
+
+use crate::{align_down, process_vm_readv, Error, ProcessVirtualMemoryIO, Result};
+
+/// Iterator over the lines of text stored in an address range of the virtual
+/// memory of a process, as returned by [`ProcessVirtualMemoryIO::lines`].
+#[derive(Debug)]
+pub struct RemoteLines<'a> {
+    io: &'a mut ProcessVirtualMemoryIO,
+
+    /// Remaining address range to read.
+    range: Range<u64>,
+
+    /// Data read but not yet returned as lines.
+    pending: Vec<u8>,
+
+    /// Error to return before the end of iteration, if any.
+    error: Option<Error>,
+}
+
+impl<'a> RemoteLines<'a> {
+    pub(crate) fn new(io: &'a mut ProcessVirtualMemoryIO, range: Range<u64>) -> Self {
+        let error = io
+            .extent_of(
+                range.clone(),
+                "process_vm_io::ProcessVirtualMemoryIO::lines",
+            )
+            .err();
+
+        Self {
+            io,
+            range,
+            pending: Vec::new(),
+            error,
+        }
+    }
+
+    /// Remove the first line from the pending data, if it is complete, or if
+    /// `last` is `true`. The line terminator is not returned.
+    fn take_line(&mut self, last: bool) -> Option<String> {
+        let end_of_line = match self.pending.iter().position(|&b| b == b'\n') {
+            Some(index) => index + 1,
+            None if last && !self.pending.is_empty() => self.pending.len(),
+            None => return None,
+        };
+
+        let mut line: Vec<u8> = self.pending.drain(..end_of_line).collect();
+        if line.last() == Some(&b'\n') {
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+        }
+        Some(String::from_utf8_lossy(&line).into_owned())
+    }
+
+    /// Read the data stored up to the next page boundary, or up to the end of
+    /// the remaining address range. Returns the number of bytes read.
+    fn read_next_chunk(&mut self) -> Result<usize> {
+        let min_page_size = self.io.page_size;
+        let address = self.range.start;
+        let chunk_end = align_down(address, min_page_size)
+            .checked_add(min_page_size)
+            .map_or(self.range.end, |next_page| {
+                cmp::min(next_page, self.range.end)
+            });
+        let size = usize::try_from(chunk_end - address)?;
+
+        let old_len = self.pending.len();
+        self.pending.resize(old_len + size, 0);
+        let local_io_vector = libc::iovec {
+            iov_base: self.pending[old_len..].as_mut_ptr() as *mut c_void,
+            iov_len: size,
+        };
+
+        let result =
+            self.io
+                .io_vectored_at(process_vm_readv, address, &[local_io_vector], size as u64);
+
+        let count = *result.as_ref().unwrap_or(&0);
+        self.pending.truncate(old_len + count);
+        self.range.start += count as u64;
+        result
+    }
+}
+
+impl Iterator for RemoteLines<'_> {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(error) = self.error.take() {
+                self.range.end = self.range.start;
+                self.pending.clear();
+                return Some(Err(error));
+            }
+
+            if let Some(line) = self.take_line(false) {
+                return Some(Ok(line));
+            }
+
+            if self.range.start >= self.range.end {
+                return self.take_line(true).map(Ok);
+            }
+
+            match self.read_next_chunk() {
+                Ok(0) => self.range.end = self.range.start,
+                Ok(_) => {}
+                Err(err) => self.error = Some(err),
+            }
+        }
+    }
+}
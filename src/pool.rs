@@ -0,0 +1,116 @@
+// Copyright (c) 2020-2024 MicroDoc Software GmbH.
+// See the "LICENSE.txt" file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license. This file may not be copied, modified,
+// or distributed except according to those terms.
+
+/*! Reuse of scratch buffers across data transfers. */
+
+use std::mem;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Pool of scratch buffers, from which the methods of
+/// [`ProcessVirtualMemoryIO`](crate::ProcessVirtualMemoryIO) that need
+/// temporary buffers draw them, and to which they return them, if set by
+/// [`ProcessVirtualMemoryIO::set_buffer_pool`](crate::ProcessVirtualMemoryIO::set_buffer_pool).
+///
+/// Those methods, e.g.,
+/// [`read_cstr`](crate::ProcessVirtualMemoryIO::read_cstr) or
+/// [`dump_region`](crate::ProcessVirtualMemoryIO::dump_region), otherwise
+/// allocate their scratch buffers, typically of a page or a few pages, on
+/// each call, which becomes measurable when calling them at a high rate.
+/// Buffers keep their capacity while in the pool, so that, once the pool is
+/// warm, such calls do not allocate scratch buffers anymore.
+///
+/// A pool can be shared by several instances, possibly used by several
+/// threads.
+#[derive(Debug)]
+pub struct BufferPool {
+    /// Maximum number of buffers kept while not in use.
+    max_idle: usize,
+
+    /// Buffers not in use.
+    idle: Mutex<Vec<Vec<u8>>>,
+}
+
+impl BufferPool {
+    /// Create an empty pool, keeping at most `max_idle` buffers while they
+    /// are not in use. Buffers returned beyond that are deallocated.
+    pub fn new(max_idle: usize) -> Self {
+        Self {
+            max_idle,
+            idle: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Return the number of buffers kept by the pool while not in use.
+    pub fn idle_count(&self) -> usize {
+        self.lock_idle().len()
+    }
+
+    /// Draw a buffer of `len` zero bytes from the pool, or allocate it if the
+    /// pool is empty.
+    fn take(&self, len: usize) -> Vec<u8> {
+        let mut buffer = self.lock_idle().pop().unwrap_or_default();
+        buffer.clear();
+        buffer.resize(len, 0);
+        buffer
+    }
+
+    /// Return `buffer` to the pool, unless the pool is full.
+    fn put(&self, buffer: Vec<u8>) {
+        let mut idle = self.lock_idle();
+        if idle.len() < self.max_idle {
+            idle.push(buffer);
+        }
+    }
+
+    fn lock_idle(&self) -> MutexGuard<'_, Vec<Vec<u8>>> {
+        // The buffers remain usable even if a thread panicked while holding
+        // the lock.
+        self.idle.lock().unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+/// Scratch buffer drawn from a [`BufferPool`], if any, and returned to it when
+/// dropped.
+#[derive(Debug)]
+pub(crate) struct ScratchBuffer {
+    pool: Option<Arc<BufferPool>>,
+    buffer: Vec<u8>,
+}
+
+impl ScratchBuffer {
+    /// Draw a buffer of `len` zero bytes from `pool`, or allocate it if there
+    /// is no pool.
+    pub(crate) fn new(pool: Option<&Arc<BufferPool>>, len: usize) -> Self {
+        let buffer = pool.map_or_else(|| vec![0_u8; len], |pool| pool.take(len));
+        Self {
+            pool: pool.cloned(),
+            buffer,
+        }
+    }
+}
+
+impl Deref for ScratchBuffer {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.buffer
+    }
+}
+
+impl DerefMut for ScratchBuffer {
+    fn deref_mut(&mut self) -> &mut [u8] {
+        &mut self.buffer
+    }
+}
+
+impl Drop for ScratchBuffer {
+    fn drop(&mut self) {
+        if let Some(pool) = &self.pool {
+            pool.put(mem::take(&mut self.buffer));
+        }
+    }
+}
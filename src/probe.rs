@@ -0,0 +1,114 @@
+// Copyright (c) 2020-2024 MicroDoc Software GmbH.
+// See the "LICENSE.txt" file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license. This file may not be copied, modified,
+// or distributed except according to those terms.
+
+/*! Probing of the means of accessing the virtual memory of processes. */
+
+use std::ffi::{c_void, CString};
+use std::os::raw::c_uint;
+use std::{fs, io, ptr};
+
+use crate::{process_vm_readv, procfs};
+
+/// Means of accessing the virtual memory of a process that are available in
+/// the current environment, as returned by [`probe_capabilities`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// The `process_vm_readv()` system call can read the virtual memory of
+    /// the process.
+    pub process_vm_readv: bool,
+    /// The file `/proc/[pid]/mem` of the process can be opened for reading.
+    pub proc_mem: bool,
+    /// The `pidfd_open()` system call can open a file descriptor referring to
+    /// the process.
+    pub pidfd: bool,
+    /// The process can probably be traced by `ptrace()`.
+    pub ptrace: bool,
+}
+
+/// Determine which means of accessing the virtual memory of the process
+/// identified by `process_id` are available in the current environment.
+///
+/// Each means is tried minimally, without modifying the process:
+/// - `process_vm_readv()` is tried by reading one byte at the address zero,
+///   which is normally not mapped. Failing to read because of that, i.e.,
+///   with an `EFAULT` errno code, still shows that reading is permitted.
+/// - `/proc/[pid]/mem` is opened for reading, then closed.
+/// - `pidfd_open()` is called, and the returned file descriptor is closed.
+///
+/// Attaching to the process with `ptrace()` would stop it, so this is instead
+/// inferred: the process must be readable by `process_vm_readv()`, as both
+/// require the same access rights, must not be the current process, must not
+/// be traced already, and the Yama security module must not forbid attaching.
+///
+/// This helps choosing how to access the process, or reporting insufficient
+/// privileges clearly, before starting to work on the process.
+pub fn probe_capabilities(process_id: u32) -> Capabilities {
+    let pid = process_id as libc::pid_t;
+    if pid <= 0 {
+        return Capabilities::default();
+    }
+
+    let process_vm_readv = probe_process_vm_readv(pid);
+
+    let ptrace = process_vm_readv
+        && process_id != std::process::id()
+        && procfs::status(pid).is_ok_and(|status| status.tracer_process_id == Some(0))
+        && fs::read_to_string("/proc/sys/kernel/yama/ptrace_scope")
+            .map_or(true, |scope| scope.trim() != "3");
+
+    Capabilities {
+        process_vm_readv,
+        proc_mem: probe_proc_mem(pid),
+        pidfd: probe_pidfd(pid),
+        ptrace,
+    }
+}
+
+/// Determine whether `process_vm_readv()` can read the virtual memory of the
+/// process identified by `process_id`.
+fn probe_process_vm_readv(process_id: libc::pid_t) -> bool {
+    let mut byte = 0_u8;
+    let local_io_vector = libc::iovec {
+        iov_base: ptr::addr_of_mut!(byte) as *mut c_void,
+        iov_len: 1,
+    };
+    let remote_io_vector = libc::iovec {
+        iov_base: ptr::null_mut(),
+        iov_len: 1,
+    };
+
+    let result =
+        unsafe { process_vm_readv(process_id, &local_io_vector, 1, &remote_io_vector, 1, 0) };
+    result != -1 || io::Error::last_os_error().raw_os_error() == Some(libc::EFAULT)
+}
+
+/// Determine whether the file `/proc/[process_id]/mem` can be opened for
+/// reading.
+fn probe_proc_mem(process_id: libc::pid_t) -> bool {
+    let Ok(path) = CString::new(format!("/proc/{process_id}/mem")) else {
+        return false;
+    };
+
+    let fd = unsafe { libc::open(path.as_ptr(), libc::O_RDONLY | libc::O_CLOEXEC) };
+    if fd == -1 {
+        return false;
+    }
+    unsafe { libc::close(fd) };
+    true
+}
+
+/// Determine whether `pidfd_open()` can open a file descriptor referring to
+/// the process identified by `process_id`.
+fn probe_pidfd(process_id: libc::pid_t) -> bool {
+    let flags: c_uint = 0;
+    let fd = unsafe { libc::syscall(libc::SYS_pidfd_open, process_id, flags) };
+    if fd == -1 {
+        return false;
+    }
+    unsafe { libc::close(fd as libc::c_int) };
+    true
+}
@@ -0,0 +1,643 @@
+// Copyright (c) 2020-2024 MicroDoc Software GmbH.
+// See the "LICENSE.txt" file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license. This file may not be copied, modified,
+// or distributed except according to those terms.
+
+/*! Inspection of processes through the `/proc` file system. */
+
+use std::collections::BTreeMap;
+use std::os::fd::RawFd;
+use std::path::PathBuf;
+use std::{fs, io};
+
+use crate::{Error, Result};
+
+/// Liveness state of a process, as reported by `/proc/[pid]/stat`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Liveness {
+    /// The process is running, or sleeping in an interruptible or
+    /// uninterruptible wait. Its virtual memory can be accessed.
+    Running,
+
+    /// The process terminated but was not yet reaped by its parent.
+    /// It can still receive signals, but its virtual memory is gone.
+    Zombie,
+
+    /// The process is stopped by a signal, or is being traced.
+    /// Its virtual memory can be accessed.
+    Stopped,
+
+    /// The process does not exist.
+    Dead,
+}
+
+impl Liveness {
+    /// Map a process state character, as reported by `/proc/[pid]/stat`,
+    /// into a liveness state.
+    fn from_state_char(state: char) -> Self {
+        match state {
+            'Z' => Self::Zombie,
+            'T' | 't' => Self::Stopped,
+            'X' | 'x' => Self::Dead,
+            _ => Self::Running,
+        }
+    }
+}
+
+/// Read the contents of the file `/proc/[process_id]/stat`, and return the
+/// fields that follow the command name, starting with the process state.
+///
+/// The command name is skipped because it might contain spaces and parentheses.
+pub(crate) fn read_stat_fields(process_id: libc::pid_t) -> Result<Vec<String>> {
+    let path = format!("/proc/{process_id}/stat");
+    let contents = fs::read_to_string(path)
+        .map_err(|err| Error::from_io3(err, "read(/proc/[pid]/stat)", process_id))?;
+
+    let fields = contents
+        .rfind(')')
+        .map(|end_of_command| &contents[end_of_command + 1..])
+        .map(|rest| rest.split_ascii_whitespace().map(String::from).collect())
+        .unwrap_or_default();
+    Ok(fields)
+}
+
+/// Determine whether the process identified by `process_id` belongs to the
+/// thread group of the current process, i.e., whether it is the current
+/// process or one of its threads, whose thread identifiers are accepted
+/// wherever process identifiers are.
+pub(crate) fn is_current_thread_group(process_id: libc::pid_t) -> Result<bool> {
+    let current_process_id = std::process::id();
+    if process_id as u32 == current_process_id {
+        return Ok(true);
+    }
+    Ok(status(process_id)?.thread_group_id == Some(current_process_id))
+}
+
+/// Determine the liveness state of the process identified by `process_id`.
+pub(crate) fn liveness(process_id: libc::pid_t) -> Result<Liveness> {
+    let fields = match read_stat_fields(process_id) {
+        Ok(fields) => fields,
+
+        Err(err) => {
+            return match err.kind() {
+                crate::ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::NotFound => {
+                    Ok(Liveness::Dead)
+                }
+                _ => Err(err),
+            }
+        }
+    };
+
+    fields
+        .first()
+        .and_then(|state| state.chars().next())
+        .map(Liveness::from_state_char)
+        .ok_or_else(|| {
+            Error::from_io3(
+                io::ErrorKind::InvalidData.into(),
+                "read(/proc/[pid]/stat)",
+                process_id,
+            )
+        })
+}
+
+/// Access permissions of a memory region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Permissions {
+    /// The region can be read.
+    pub read: bool,
+    /// The region can be written.
+    pub write: bool,
+    /// The region can be executed.
+    pub execute: bool,
+    /// The region is shared with other processes, instead of being private
+    /// (copy on write).
+    pub shared: bool,
+}
+
+/// A memory region mapped in the virtual address space of a process,
+/// as described by `/proc/[pid]/maps`.
+///
+/// Memory regions are ordered by start address, then by end address, then by
+/// the remaining fields.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MemoryRegion {
+    /// Address of the first byte of the region.
+    pub start: u64,
+    /// Address following the last byte of the region.
+    pub end: u64,
+    /// Access permissions of the region.
+    pub perms: Permissions,
+    /// Offset of the region in the mapped file, if any.
+    pub offset: u64,
+    /// Major and minor numbers of the device holding the mapped file, if any.
+    pub dev: (u32, u32),
+    /// Inode of the mapped file on its device, if any.
+    pub inode: u64,
+    /// Path of the mapped file, or a pseudo-path such as `[heap]`, `[stack]`
+    /// or `[vdso]`. Anonymous mappings have no path.
+    pub pathname: Option<String>,
+}
+
+impl MemoryRegion {
+    /// Parse a line of `/proc/[pid]/maps`.
+    fn parse(line: &str) -> Option<Self> {
+        // Fields are separated by exactly one space, except for the path name
+        // which is padded by multiple spaces, and might contain spaces itself.
+        let mut fields = line.splitn(6, ' ');
+
+        let (start, end) = fields.next()?.split_once('-')?;
+        let perms = fields.next()?.as_bytes();
+        let offset = fields.next()?;
+        let (dev_major, dev_minor) = fields.next()?.split_once(':')?;
+        let inode = fields.next()?;
+        let pathname = fields.next().map(str::trim_start).unwrap_or_default();
+
+        if perms.len() != 4 {
+            return None;
+        }
+
+        Some(Self {
+            start: u64::from_str_radix(start, 16).ok()?,
+            end: u64::from_str_radix(end, 16).ok()?,
+            perms: Permissions {
+                read: perms[0] == b'r',
+                write: perms[1] == b'w',
+                execute: perms[2] == b'x',
+                shared: perms[3] == b's',
+            },
+            offset: u64::from_str_radix(offset, 16).ok()?,
+            dev: (
+                u32::from_str_radix(dev_major, 16).ok()?,
+                u32::from_str_radix(dev_minor, 16).ok()?,
+            ),
+            inode: inode.parse().ok()?,
+            pathname: (!pathname.is_empty()).then(|| pathname.to_owned()),
+        })
+    }
+}
+
+/// Differences between two snapshots of the memory regions of a process,
+/// as computed by [`diff_maps`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct MapDiff {
+    /// Regions whose address ranges appear only in the new snapshot.
+    pub added: Vec<MemoryRegion>,
+    /// Regions whose address ranges appear only in the old snapshot.
+    pub removed: Vec<MemoryRegion>,
+    /// Regions whose address ranges appear in both snapshots, but with other
+    /// attributes (e.g., permissions or path) that differ, as pairs of old and
+    /// new regions.
+    pub changed: Vec<(MemoryRegion, MemoryRegion)>,
+}
+
+impl MapDiff {
+    /// Returns `true` if both snapshots describe the same memory regions.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Compare two snapshots of the memory regions of a process, as returned by
+/// [`ProcessVirtualMemoryIO::memory_maps`](crate::ProcessVirtualMemoryIO::memory_maps).
+///
+/// Regions are matched by their address ranges. All returned lists are sorted
+/// by address.
+pub fn diff_maps(old: &[MemoryRegion], new: &[MemoryRegion]) -> MapDiff {
+    let mut old_regions: BTreeMap<(u64, u64), &MemoryRegion> = old
+        .iter()
+        .map(|region| ((region.start, region.end), region))
+        .collect();
+
+    let mut diff = MapDiff::default();
+    for new_region in new {
+        match old_regions.remove(&(new_region.start, new_region.end)) {
+            None => diff.added.push(new_region.clone()),
+            Some(old_region) if old_region != new_region => {
+                diff.changed.push((old_region.clone(), new_region.clone()))
+            }
+            Some(_) => {}
+        }
+    }
+    diff.removed = old_regions.into_values().cloned().collect();
+
+    diff.added.sort_unstable();
+    diff.changed.sort_unstable();
+    diff
+}
+
+/// Parse the contents of a `/proc/[pid]/maps` file.
+///
+/// The file is read by several system calls, between which the process might
+/// change its mappings, so the same region might be described twice, or
+/// regions might overlap. Regions are sorted by address, and each region
+/// that overlaps a previous one is dropped.
+pub(crate) fn parse_memory_maps(contents: &str) -> Option<Vec<MemoryRegion>> {
+    let mut regions = contents
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(MemoryRegion::parse)
+        .collect::<Option<Vec<_>>>()?;
+
+    regions.sort_by_key(|region| region.start);
+    regions.dedup_by(|region, previous| region.start < previous.end);
+    Some(regions)
+}
+
+/// Read and parse the file `/proc/[process_id]/maps`.
+pub(crate) fn memory_maps(process_id: libc::pid_t) -> Result<Vec<MemoryRegion>> {
+    let path = format!("/proc/{process_id}/maps");
+    let contents = fs::read_to_string(path)
+        .map_err(|err| Error::from_io3(err, "read(/proc/[pid]/maps)", process_id))?;
+
+    parse_memory_maps(&contents).ok_or_else(|| {
+        Error::from_io3(
+            io::ErrorKind::InvalidData.into(),
+            "read(/proc/[pid]/maps)",
+            process_id,
+        )
+    })
+}
+
+/// Aggregate memory usage of a process, as described by
+/// `/proc/[pid]/smaps_rollup`.
+///
+/// All sizes are in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub struct MemorySummary {
+    /// Resident set size, i.e., size of the memory currently in RAM.
+    pub rss: u64,
+    /// Proportional set size, i.e., resident memory where each page shared
+    /// with other processes is divided by the number of sharing processes.
+    pub pss: u64,
+    /// Resident memory shared with other processes, and not modified.
+    pub shared_clean: u64,
+    /// Resident memory shared with other processes, and modified.
+    pub shared_dirty: u64,
+    /// Resident memory private to the process, and not modified.
+    pub private_clean: u64,
+    /// Resident memory private to the process, and modified.
+    pub private_dirty: u64,
+    /// Memory swapped out.
+    pub swap: u64,
+}
+
+impl MemorySummary {
+    /// Return the field of this summary that corresponds to the given key of
+    /// `/proc/[pid]/smaps_rollup`, if any.
+    fn field_mut(&mut self, key: &str) -> Option<&mut u64> {
+        match key {
+            "Rss" => Some(&mut self.rss),
+            "Pss" => Some(&mut self.pss),
+            "Shared_Clean" => Some(&mut self.shared_clean),
+            "Shared_Dirty" => Some(&mut self.shared_dirty),
+            "Private_Clean" => Some(&mut self.private_clean),
+            "Private_Dirty" => Some(&mut self.private_dirty),
+            "Swap" => Some(&mut self.swap),
+            _ => None,
+        }
+    }
+}
+
+/// Parse the contents of a `/proc/[pid]/smaps_rollup` file, or of a
+/// `/proc/[pid]/smaps` file, in which case the sizes of all memory regions
+/// are summed.
+pub(crate) fn parse_memory_summary(contents: &str) -> Option<MemorySummary> {
+    let mut summary = MemorySummary::default();
+    for line in contents.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        if let Some(field) = summary.field_mut(key) {
+            let size_in_kib: u64 = value.trim().strip_suffix(" kB")?.parse().ok()?;
+            *field = field.checked_add(size_in_kib.checked_mul(1024)?)?;
+        }
+    }
+    Some(summary)
+}
+
+/// Read and parse the file `/proc/[process_id]/smaps_rollup`, or the file
+/// `/proc/[process_id]/smaps` on kernels that do not provide the former.
+pub(crate) fn memory_summary(process_id: libc::pid_t) -> Result<MemorySummary> {
+    let (contents, operation) = match fs::read_to_string(format!("/proc/{process_id}/smaps_rollup"))
+    {
+        Ok(contents) => (contents, "read(/proc/[pid]/smaps_rollup)"),
+
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            let contents = fs::read_to_string(format!("/proc/{process_id}/smaps"))
+                .map_err(|err| Error::from_io3(err, "read(/proc/[pid]/smaps)", process_id))?;
+            (contents, "read(/proc/[pid]/smaps)")
+        }
+
+        Err(err) => {
+            return Err(Error::from_io3(
+                err,
+                "read(/proc/[pid]/smaps_rollup)",
+                process_id,
+            ))
+        }
+    };
+
+    parse_memory_summary(&contents)
+        .ok_or_else(|| Error::from_io3(io::ErrorKind::InvalidData.into(), operation, process_id))
+}
+
+/// A set of signals, as a bit mask where signal number `n` is represented by
+/// the bit `n - 1`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct SignalSet(pub u64);
+
+impl SignalSet {
+    /// Returns `true` if this set contains the signal number `signal`,
+    /// e.g., [`libc::SIGTERM`].
+    pub fn contains(self, signal: libc::c_int) -> bool {
+        (1..=64).contains(&signal) && (self.0 & (1 << (signal - 1))) != 0
+    }
+}
+
+/// Signal-related state of a process, as described by `/proc/[pid]/status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub struct SignalState {
+    /// Number of signals queued for the real user ID of the process.
+    pub queued: u64,
+    /// Maximum number of signals that can be queued for the real user ID of
+    /// the process.
+    pub queue_limit: u64,
+    /// Signals pending for the thread.
+    pub pending: SignalSet,
+    /// Signals blocked by the thread.
+    pub blocked: SignalSet,
+    /// Signals ignored by the process.
+    pub ignored: SignalSet,
+    /// Signals caught by a handler of the process.
+    pub caught: SignalSet,
+}
+
+/// Parse the signal-related fields of the contents of a `/proc/[pid]/status`
+/// file.
+pub(crate) fn parse_signal_state(contents: &str) -> Option<SignalState> {
+    let field = |name: &str| {
+        contents.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key == name).then(|| value.trim())
+        })
+    };
+    let signal_set = |name: &str| u64::from_str_radix(field(name)?, 16).ok().map(SignalSet);
+
+    let (queued, queue_limit) = field("SigQ")?.split_once('/')?;
+    Some(SignalState {
+        queued: queued.parse().ok()?,
+        queue_limit: queue_limit.parse().ok()?,
+        pending: signal_set("SigPnd")?,
+        blocked: signal_set("SigBlk")?,
+        ignored: signal_set("SigIgn")?,
+        caught: signal_set("SigCgt")?,
+    })
+}
+
+/// Read the file `/proc/[process_id]/status`, and parse its signal-related
+/// fields.
+pub(crate) fn signal_state(process_id: libc::pid_t) -> Result<SignalState> {
+    let path = format!("/proc/{process_id}/status");
+    let contents = fs::read_to_string(path)
+        .map_err(|err| Error::from_io3(err, "read(/proc/[pid]/status)", process_id))?;
+
+    parse_signal_state(&contents).ok_or_else(|| {
+        Error::from_io3(
+            io::ErrorKind::InvalidData.into(),
+            "read(/proc/[pid]/status)",
+            process_id,
+        )
+    })
+}
+
+/// Status of a process, as described by `/proc/[pid]/status`.
+///
+/// Fields that are absent from the file, e.g., on older kernels, or that
+/// cannot be parsed, are `None`. Sizes are in bytes.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub struct ProcStatus {
+    /// Command name of the process, possibly truncated.
+    pub name: Option<String>,
+    /// State of the process, as a letter, e.g., `'R'` when running, or
+    /// `'S'` when sleeping.
+    pub state: Option<char>,
+    /// Identifier of the thread group, i.e., the process identifier.
+    pub thread_group_id: Option<u32>,
+    /// Process identifier of the parent process, or zero if there is none.
+    pub parent_process_id: Option<u32>,
+    /// Process identifier of the tracer of the process, or zero if there is
+    /// none.
+    pub tracer_process_id: Option<u32>,
+    /// Real user identifier.
+    pub uid: Option<u32>,
+    /// Effective user identifier.
+    pub effective_uid: Option<u32>,
+    /// Real group identifier.
+    pub gid: Option<u32>,
+    /// Effective group identifier.
+    pub effective_gid: Option<u32>,
+    /// Peak size of the virtual memory.
+    pub vm_peak: Option<u64>,
+    /// Size of the virtual memory.
+    pub vm_size: Option<u64>,
+    /// Peak resident set size.
+    pub vm_hwm: Option<u64>,
+    /// Resident set size.
+    pub vm_rss: Option<u64>,
+    /// Size of the memory swapped out.
+    pub vm_swap: Option<u64>,
+    /// Number of threads.
+    pub threads: Option<u32>,
+    /// Signal-related state.
+    pub signals: Option<SignalState>,
+}
+
+/// Parse the contents of a `/proc/[pid]/status` file.
+pub(crate) fn parse_status(contents: &str) -> ProcStatus {
+    let field = |name: &str| {
+        contents.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key == name).then(|| value.trim())
+        })
+    };
+    let number = |name: &str| field(name)?.parse().ok();
+    let nth_id =
+        |name: &str, index: usize| field(name)?.split_whitespace().nth(index)?.parse().ok();
+    let size = |name: &str| {
+        let size_in_kib: u64 = field(name)?.strip_suffix(" kB")?.trim().parse().ok()?;
+        size_in_kib.checked_mul(1024)
+    };
+
+    ProcStatus {
+        name: field("Name").map(str::to_owned),
+        state: field("State").and_then(|state| state.chars().next()),
+        thread_group_id: number("Tgid"),
+        parent_process_id: number("PPid"),
+        tracer_process_id: number("TracerPid"),
+        uid: nth_id("Uid", 0),
+        effective_uid: nth_id("Uid", 1),
+        gid: nth_id("Gid", 0),
+        effective_gid: nth_id("Gid", 1),
+        vm_peak: size("VmPeak"),
+        vm_size: size("VmSize"),
+        vm_hwm: size("VmHWM"),
+        vm_rss: size("VmRSS"),
+        vm_swap: size("VmSwap"),
+        threads: number("Threads"),
+        signals: parse_signal_state(contents),
+    }
+}
+
+/// Read and parse the file `/proc/[process_id]/status`.
+pub(crate) fn status(process_id: libc::pid_t) -> Result<ProcStatus> {
+    let path = format!("/proc/{process_id}/status");
+    let contents = fs::read_to_string(path)
+        .map_err(|err| Error::from_io3(err, "read(/proc/[pid]/status)", process_id))?;
+    Ok(parse_status(&contents))
+}
+
+/// Scheduling statistics of a process, as described by
+/// `/proc/[pid]/schedstat`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub struct SchedStats {
+    /// Time spent running on a CPU, in nanoseconds.
+    pub run_time_ns: u64,
+    /// Time spent waiting on a run queue, in nanoseconds.
+    pub wait_time_ns: u64,
+    /// Number of time slices run on a CPU.
+    pub timeslices: u64,
+}
+
+/// Parse the contents of a `/proc/[pid]/wchan` file.
+///
+/// A process that is not sleeping has no wait channel, reported as `0` or
+/// as an empty file.
+pub(crate) fn parse_wait_channel(contents: &str) -> Option<String> {
+    let symbol = contents.trim();
+    (!symbol.is_empty() && symbol != "0").then(|| symbol.to_owned())
+}
+
+/// Read and parse the file `/proc/[process_id]/wchan`.
+pub(crate) fn wait_channel(process_id: libc::pid_t) -> Result<Option<String>> {
+    let path = format!("/proc/{process_id}/wchan");
+    let contents = fs::read_to_string(path)
+        .map_err(|err| Error::from_io3(err, "read(/proc/[pid]/wchan)", process_id))?;
+    Ok(parse_wait_channel(&contents))
+}
+
+/// Parse the contents of a `/proc/[pid]/schedstat` file.
+pub(crate) fn parse_sched_stats(contents: &str) -> Option<SchedStats> {
+    let mut fields = contents.split_ascii_whitespace().map(str::parse);
+    Some(SchedStats {
+        run_time_ns: fields.next()?.ok()?,
+        wait_time_ns: fields.next()?.ok()?,
+        timeslices: fields.next()?.ok()?,
+    })
+}
+
+/// Read and parse the file `/proc/[process_id]/schedstat`.
+pub(crate) fn sched_stats(process_id: libc::pid_t) -> Result<SchedStats> {
+    let path = format!("/proc/{process_id}/schedstat");
+    let contents = fs::read_to_string(path)
+        .map_err(|err| Error::from_io3(err, "read(/proc/[pid]/schedstat)", process_id))?;
+
+    parse_sched_stats(&contents).ok_or_else(|| {
+        Error::from_io3(
+            io::ErrorKind::InvalidData.into(),
+            "read(/proc/[pid]/schedstat)",
+            process_id,
+        )
+    })
+}
+
+/// List the file descriptors opened by the process identified by `process_id`,
+/// along with the targets of the symbolic links `/proc/[process_id]/fd/*`.
+pub(crate) fn open_fds(process_id: libc::pid_t) -> Result<Vec<(u32, PathBuf)>> {
+    let path = format!("/proc/{process_id}/fd");
+    let entries = fs::read_dir(&path)
+        .map_err(|err| Error::from_io3(err, "opendir(/proc/[pid]/fd)", process_id))?;
+
+    let mut result = Vec::new();
+    for entry in entries {
+        let entry =
+            entry.map_err(|err| Error::from_io3(err, "readdir(/proc/[pid]/fd)", process_id))?;
+
+        let fd = match entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse().ok())
+        {
+            Some(fd) => fd,
+            None => continue,
+        };
+
+        match fs::read_link(entry.path()) {
+            Ok(target) => result.push((fd, target)),
+
+            // The file descriptor was closed after listing the directory.
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+
+            Err(err) => {
+                return Err(Error::from_io3(
+                    err,
+                    "readlink(/proc/[pid]/fd/*)",
+                    process_id,
+                ))
+            }
+        }
+    }
+
+    result.sort_unstable_by_key(|(fd, _)| *fd);
+    Ok(result)
+}
+
+/// Parse the contents of a `/proc/self/fdinfo/[fd]` file, and return the
+/// process identifier of its `Pid:` field, which only file descriptors
+/// referring to processes, i.e., `pidfd`s, have.
+///
+/// The process identifier is `-1` if the process exited, or `0` if it is not
+/// visible in the PID namespace of the current process.
+pub(crate) fn parse_pidfd_process_id(contents: &str) -> Option<libc::pid_t> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("Pid:"))
+        .and_then(|value| value.trim().parse().ok())
+}
+
+/// Read the file `/proc/self/fdinfo/[fd]`, and return the process identifier
+/// of the process that the `pidfd` `fd` refers to, as parsed by
+/// [`parse_pidfd_process_id`].
+pub(crate) fn pidfd_process_id(fd: RawFd) -> Result<Option<libc::pid_t>> {
+    let path = format!("/proc/self/fdinfo/{fd}");
+    let contents = fs::read_to_string(path)
+        .map_err(|err| Error::from_io2(err, "read(/proc/self/fdinfo/[fd])"))?;
+    Ok(parse_pidfd_process_id(&contents))
+}
+
+/// Read the start time of the process identified by `process_id`, in clock
+/// ticks since the system booted, from the file `/proc/[process_id]/stat`.
+///
+/// Unlike the process identifier, the start time of a process is not shared
+/// with a process that reuses that identifier later, unless both start within
+/// the same clock tick.
+pub(crate) fn start_time(process_id: libc::pid_t) -> Result<u64> {
+    // The start time is the 22nd field, counting from the process identifier.
+    read_stat_fields(process_id)?
+        .get(19)
+        .and_then(|field| field.parse().ok())
+        .ok_or_else(|| {
+            Error::from_io3(
+                io::ErrorKind::InvalidData.into(),
+                "read(/proc/[pid]/stat)",
+                process_id,
+            )
+        })
+}
@@ -0,0 +1,51 @@
+// Copyright (c) 2020-2024 MicroDoc Software GmbH.
+// See the "LICENSE.txt" file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license. This file may not be copied, modified,
+// or distributed except according to those terms.
+
+/*! Packed images of sparsely mapped address ranges. */
+
+use std::ops::Range;
+
+/// Data read from the readable parts of an address range, packed into one
+/// buffer, as returned by
+/// [`ProcessVirtualMemoryIO::read_all_into`](crate::ProcessVirtualMemoryIO::read_all_into).
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub struct SparseImage {
+    /// Concatenated data of all readable parts of the address range.
+    pub data: Vec<u8>,
+
+    /// Address of each readable part, along with the range of `data` holding
+    /// its data, in order.
+    pub segments: Vec<(u64, Range<usize>)>,
+}
+
+impl SparseImage {
+    /// Return the address from which the byte at `buffer_offset` in
+    /// [`data`](Self::data) was read, if any.
+    pub fn address_of(&self, buffer_offset: usize) -> Option<u64> {
+        let index = self
+            .segments
+            .partition_point(|(_, range)| range.end <= buffer_offset);
+
+        self.segments
+            .get(index)
+            .filter(|(_, range)| range.start <= buffer_offset)
+            .map(|(address, range)| address + (buffer_offset - range.start) as u64)
+    }
+
+    /// Return the `len` bytes read from `address`, if they are all stored in
+    /// [`data`](Self::data), i.e., if they were read from one readable part.
+    pub fn get(&self, address: u64, len: usize) -> Option<&[u8]> {
+        let index = self
+            .segments
+            .partition_point(|(start, range)| start + range.len() as u64 <= address);
+
+        let (start, range) = self.segments.get(index)?;
+        let offset = usize::try_from(address.checked_sub(*start)?).ok()?;
+        let end = offset.checked_add(len)?;
+        (end <= range.len()).then(|| &self.data[range.start + offset..range.start + end])
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright (c) 2020-2024 MicroDoc Software GmbH.
+// See the "LICENSE.txt" file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license. This file may not be copied, modified,
+// or distributed except according to those terms.
+
+/*! Strings stored in the virtual memory of a process. */
+
+/// String read from the virtual memory of a process up to its NUL terminator,
+/// as returned by
+/// [`ProcessVirtualMemoryIO::read_cstr`](crate::ProcessVirtualMemoryIO::read_cstr)
+/// and [`ProcessVirtualMemoryIO::read_utf16_cstr`](crate::ProcessVirtualMemoryIO::read_utf16_cstr).
+///
+/// The string is made of code units of type `T`, e.g., bytes or UTF-16 code
+/// units.
+/// The NUL terminator is never included.
+///
+/// A plain vector of code units could not tell a string that was cut at the
+/// maximum length apart from a string of that length followed by its NUL
+/// terminator, which callers need to know, e.g., to read the rest of the
+/// string. [`into_inner`](Self::into_inner) returns the code units when this
+/// does not matter.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RemoteString<T> {
+    /// The string ended with a NUL terminator.
+    Terminated(Vec<T>),
+
+    /// The maximum length was reached before any NUL terminator.
+    /// The actual string might be longer.
+    Truncated(Vec<T>),
+}
+
+impl<T> RemoteString<T> {
+    /// Return `true` if the whole string was read, up to its NUL terminator.
+    pub fn is_terminated(&self) -> bool {
+        matches!(self, Self::Terminated(_))
+    }
+
+    /// Return the code units of the string that were read.
+    pub fn as_slice(&self) -> &[T] {
+        match self {
+            Self::Terminated(units) | Self::Truncated(units) => units,
+        }
+    }
+
+    /// Return the code units of the string that were read, whether the string
+    /// was terminated or not.
+    pub fn into_inner(self) -> Vec<T> {
+        match self {
+            Self::Terminated(units) | Self::Truncated(units) => units,
+        }
+    }
+}
@@ -125,6 +125,74 @@ fn new_page_aware_address_range_1page() {
     );
 }
 
+#[test]
+fn page_aware_address_range_single_page_edges() {
+    for page_size in [0x1000, 0x4000, 0x1_0000, *MIN_SYSTEM_PAGE_SIZE] {
+        let base = 0x1000_0000;
+
+        // An aligned full page is an inner page.
+        assert_eq!(
+            PageAwareAddressRange::with_page_size(base, page_size, page_size),
+            PageAwareAddressRange {
+                start_address: base,
+                size_of_inner_pages: page_size,
+                ..Default::default()
+            }
+        );
+
+        // An unaligned range of exactly one page size spans two partial pages.
+        for offset in [1, 8, page_size / 2, page_size - 1] {
+            assert_eq!(
+                PageAwareAddressRange::with_page_size(base + offset, page_size, page_size),
+                PageAwareAddressRange {
+                    start_address: base + offset,
+                    size_in_first_page: page_size - offset,
+                    size_of_inner_pages: 0,
+                    size_in_last_page: offset,
+                }
+            );
+        }
+
+        // Ranges within one page, up to its end, stay in the first page.
+        assert_eq!(
+            PageAwareAddressRange::with_page_size(base + 8, page_size - 8, page_size),
+            PageAwareAddressRange {
+                start_address: base + 8,
+                size_in_first_page: page_size - 8,
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            PageAwareAddressRange::with_page_size(base, page_size - 1, page_size),
+            PageAwareAddressRange {
+                start_address: base,
+                size_in_first_page: page_size - 1,
+                ..Default::default()
+            }
+        );
+
+        // One more byte crosses into the next page.
+        assert_eq!(
+            PageAwareAddressRange::with_page_size(base + 8, page_size - 7, page_size),
+            PageAwareAddressRange {
+                start_address: base + 8,
+                size_in_first_page: page_size - 8,
+                size_of_inner_pages: 0,
+                size_in_last_page: 1,
+            }
+        );
+        assert_eq!(
+            PageAwareAddressRange::with_page_size(base, page_size + 1, page_size),
+            PageAwareAddressRange {
+                start_address: base,
+                size_in_first_page: 0,
+                size_of_inner_pages: page_size,
+                size_in_last_page: 1,
+            }
+        );
+    }
+}
+
 #[test]
 fn new_page_aware_address_range_2pages() {
     for addr in &[
@@ -217,6 +285,108 @@ fn new_page_aware_address_range_manypages() {
     );
 }
 
+#[test]
+fn single_remote_iov_buffer_transfers_like_per_page_buffers() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    // Map four pages, then unmap the last one.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 4,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let bytes = unsafe { slice::from_raw_parts_mut(mapping as *mut u8, page_size * 3) };
+    bytes
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, b)| *b = (i % 251) as u8);
+    assert_eq!(
+        unsafe { libc::munmap(mapping.cast::<u8>().add(page_size * 3).cast(), page_size) },
+        0
+    );
+
+    let io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let page = page_size as u64;
+
+    for offset in [0, 16, page - 1, page * 3] {
+        for size in [1, 16, page, page + 1, page * 2, page * 4] {
+            let address = mapping as u64 + offset;
+
+            let (single, single_suffix) = io.remote_io_vectors(address, size).unwrap();
+            assert_eq!(single.len(), 1);
+            let (per_page, per_page_suffix) =
+                PageAwareAddressRange::with_page_size(address, size, page)
+                    .into_iov_buffers(page, io.max_iov_count())
+                    .unwrap();
+            assert_eq!(single_suffix, per_page_suffix);
+
+            let transfer = |remote_io_vectors: &[libc::iovec]| {
+                let mut buf = vec![0_u8; size as usize];
+                let local_io_vector = libc::iovec {
+                    iov_base: buf.as_mut_ptr().cast(),
+                    iov_len: buf.len(),
+                };
+                let (result, _) =
+                    io.transfer_io_vectors(process_vm_readv, &[local_io_vector], remote_io_vectors);
+                (result.map_err(|err| err.raw_os_error()), buf)
+            };
+            assert_eq!(transfer(&single), transfer(&per_page));
+        }
+    }
+
+    unsafe { libc::munmap(mapping, page_size * 3) };
+}
+
+#[test]
+fn iov_buffers_not_covered_suffix_at_iov_max() {
+    let page_size = *MIN_SYSTEM_PAGE_SIZE;
+    let range = || PageAwareAddressRange::new(0x1000_0000 - 8, 32 + page_size * 5);
+
+    let (io_vectors, suffix) = range().into_iov_buffers(page_size, 7).unwrap();
+    assert_eq!((io_vectors.len(), suffix), (7, 0));
+
+    // The last page is dropped.
+    let (io_vectors, suffix) = range().into_iov_buffers(page_size, 6).unwrap();
+    assert_eq!((io_vectors.len(), suffix), (6, 24));
+    assert_eq!(io_vectors[5].iov_len as u64, page_size);
+
+    // The last page and two inner pages are dropped.
+    let (io_vectors, suffix) = range().into_iov_buffers(page_size, 4).unwrap();
+    assert_eq!((io_vectors.len(), suffix), (4, 24 + page_size * 2));
+    let covered: u64 = io_vectors.iter().map(|v| v.iov_len as u64).sum();
+    assert_eq!(covered + suffix, 32 + page_size * 5);
+}
+
+#[test]
+fn iov_buffers_not_covered_suffix_on_allocation_failure() {
+    let page_size = *MIN_SYSTEM_PAGE_SIZE;
+    let range = || PageAwareAddressRange::new(0x1000_0000 - 8, 32 + page_size * 5);
+    let allocate_at_most = |max_count: usize| {
+        move |count: usize| (count <= max_count).then(|| SmallVec::with_capacity(count))
+    };
+
+    // The last page is dropped.
+    let (io_vectors, suffix) = range()
+        .into_iov_buffers_with(page_size, usize::MAX, allocate_at_most(6))
+        .unwrap();
+    assert_eq!((io_vectors.len(), suffix), (6, 24));
+
+    // The last page, then half the inner pages are dropped.
+    let (io_vectors, suffix) = range()
+        .into_iov_buffers_with(page_size, usize::MAX, allocate_at_most(5))
+        .unwrap();
+    assert_eq!((io_vectors.len(), suffix), (4, 24 + page_size * 2));
+    let covered: u64 = io_vectors.iter().map(|v| v.iov_len as u64).sum();
+    assert_eq!(covered + suffix, 32 + page_size * 5);
+}
+
 #[test]
 fn new_invalid_process_id() {
     assert_matches!(
@@ -260,3 +430,3670 @@ fn access_address_zero() {
         ErrorKind::Io { error, .. } if error.raw_os_error() == Some(libc::EFAULT)
     );
 }
+
+#[test]
+fn liveness_of_current_process() {
+    let process_id = std::process::id();
+    let io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    assert_eq!(io.is_alive().unwrap(), Liveness::Running);
+}
+
+#[test]
+fn liveness_of_zombie_process() {
+    let child_id = unsafe { libc::fork() };
+    assert_ne!(child_id, -1);
+    if child_id == 0 {
+        unsafe { libc::_exit(0) };
+    }
+
+    let io = unsafe { ProcessVirtualMemoryIO::new(child_id as u32, 0) }.unwrap();
+    let mut liveness = io.is_alive().unwrap();
+    for _ in 0..1000 {
+        if liveness == Liveness::Zombie {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+        liveness = io.is_alive().unwrap();
+    }
+    assert_eq!(liveness, Liveness::Zombie);
+
+    assert_eq!(
+        unsafe { libc::waitpid(child_id, ptr::null_mut(), 0) },
+        child_id
+    );
+    assert_eq!(io.is_alive().unwrap(), Liveness::Dead);
+}
+
+#[test]
+fn parse_memory_maps_lines() {
+    let regions = procfs::parse_memory_maps(
+        "00400000-00452000 r-xp 00000000 08:02 173521      /usr/bin/dbus-daemon\n\
+         00e03000-00e24000 rw-p 00000000 00:00 0           [heap]\n\
+         35b1a21000-35b1a22000 rw-p 00000000 00:00 0 \n\
+         7f2c5a000000-7f2c5a001000 rw-s 00001000 fd:01 42  /tmp/a file (deleted)\n\
+         ffffffffff600000-ffffffffff601000 --xp 00000000 00:00 0   [vsyscall]\n",
+    )
+    .unwrap();
+
+    assert_eq!(regions.len(), 5);
+    assert_eq!(
+        regions[0],
+        MemoryRegion {
+            start: 0x0040_0000,
+            end: 0x0045_2000,
+            perms: Permissions {
+                read: true,
+                execute: true,
+                ..Default::default()
+            },
+            offset: 0,
+            dev: (8, 2),
+            inode: 173_521,
+            pathname: Some("/usr/bin/dbus-daemon".into()),
+        }
+    );
+    assert_eq!(regions[1].pathname.as_deref(), Some("[heap]"));
+    assert_eq!(regions[2].pathname, None);
+    assert!(regions[3].perms.shared && regions[3].perms.write);
+    assert_eq!(regions[3].dev, (0xfd, 1));
+    assert_eq!(
+        regions[3].pathname.as_deref(),
+        Some("/tmp/a file (deleted)")
+    );
+    assert_eq!(regions[4].end, 0xffff_ffff_ff60_1000);
+
+    assert!(procfs::parse_memory_maps("00400000 r-xp 00000000 08:02 1\n").is_none());
+}
+
+#[test]
+fn parse_memory_maps_changing_between_reads() {
+    // The heap grew, and a region was mapped before it, between two reads.
+    let regions = procfs::parse_memory_maps(
+        "00400000-00452000 r-xp 00000000 08:02 173521      /usr/bin/dbus-daemon\n\
+         00e03000-00e24000 rw-p 00000000 00:00 0           [heap]\n\
+         00e03000-00e44000 rw-p 00000000 00:00 0           [heap]\n\
+         00d00000-00d01000 rw-p 00000000 00:00 0 \n\
+         7f2c5a000000-7f2c5a001000 rw-s 00001000 fd:01 42  /tmp/file\n",
+    )
+    .unwrap();
+
+    let ranges: Vec<_> = regions
+        .iter()
+        .map(|region| region.start..region.end)
+        .collect();
+    assert_eq!(
+        ranges,
+        [
+            0x0040_0000..0x0045_2000,
+            0x00d0_0000..0x00d0_1000,
+            0x00e0_3000..0x00e2_4000,
+            0x7f2c_5a00_0000..0x7f2c_5a00_1000,
+        ]
+    );
+}
+
+#[test]
+fn read_regions_in_current_process() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+
+    let data: Vec<u8> = (0..3 * *MIN_SYSTEM_PAGE_SIZE).map(|i| i as u8).collect();
+    let start = data.as_ptr() as u64;
+    let end = start + data.len() as u64;
+
+    let regions = io.read_regions_in(start..end).unwrap();
+    assert!(!regions.is_empty());
+    assert_eq!(regions.first().unwrap().0.start, start);
+    assert_eq!(regions.last().unwrap().0.end, end);
+
+    let read_data: Vec<u8> = regions.into_iter().flat_map(|(_, bytes)| bytes).collect();
+    assert_eq!(read_data, data);
+
+    assert!(io.read_regions_in(0..0).unwrap().is_empty());
+    assert!(io.read_regions_in(end..start).is_err());
+}
+
+#[test]
+fn try_seek_rejects_overflow() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0x1000) }.unwrap();
+
+    assert_eq!(io.try_seek(SeekFrom::Current(0x10)).unwrap(), 0x1010);
+    assert_eq!(io.try_seek(SeekFrom::Current(-0x1010)).unwrap(), 0);
+    assert_matches!(
+        io.try_seek(SeekFrom::Current(-1)).unwrap_err().kind(),
+        ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::InvalidInput
+    );
+    assert_eq!(io.address, Some(0));
+
+    assert_eq!(io.try_seek(SeekFrom::End(-16)).unwrap(), u64::MAX - 15);
+    assert!(io.try_seek(SeekFrom::Current(17)).is_err());
+    assert_eq!(io.address, Some(u64::MAX - 15));
+    assert_eq!(io.try_seek(SeekFrom::Current(16)).unwrap(), u64::MAX);
+    assert_eq!(io.address, None);
+    assert!(io.try_seek(SeekFrom::Current(1)).is_err());
+    assert!(io.try_seek(SeekFrom::End(1)).is_err());
+
+    assert_eq!(io.try_seek(SeekFrom::Current(-1)).unwrap(), u64::MAX);
+    assert_eq!(io.address, Some(u64::MAX));
+    assert_eq!(io.try_seek(SeekFrom::Start(u64::MAX)).unwrap(), u64::MAX);
+    assert_eq!(io.try_seek(SeekFrom::End(i64::MIN)).unwrap(), 1 << 63);
+}
+
+#[test]
+fn plan_transfer_segments() {
+    let process_id = std::process::id();
+    let io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE;
+
+    let plan = io.plan_transfer(0x1000_0000..0x1000_0000).unwrap();
+    assert!(plan.segments.is_empty());
+    assert_eq!(plan.size_of_not_covered_suffix, 0);
+
+    // Few enough pages are described by one iovec.
+    let start = 0x1000_0000 - 8;
+    let plan = io.plan_transfer(start..start + 8 + page_size + 24).unwrap();
+    assert_eq!(plan.segments, [(start, 8 + page_size as usize + 24)]);
+    assert_eq!(plan.size_of_not_covered_suffix, 0);
+
+    // More pages than one system call can access are described page by page.
+    let max_iov_count = *SYSTEM_IOV_MAX as u64;
+    let plan = io
+        .plan_transfer(start..start + page_size * (max_iov_count + 1))
+        .unwrap();
+    assert_eq!(plan.segments.len(), max_iov_count as usize);
+    assert_eq!(
+        plan.segments[..2],
+        [(start, 8), (0x1000_0000, page_size as usize)]
+    );
+    assert_eq!(
+        plan.size_of_not_covered_suffix,
+        page_size * (max_iov_count + 1)
+            - plan
+                .segments
+                .iter()
+                .map(|&(_, len)| len as u64)
+                .sum::<u64>()
+    );
+
+    assert!(io.plan_transfer(start + 1..start).is_err());
+}
+
+#[test]
+fn plan_transfer_matches_transfers() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    let data: Vec<u8> = (0..page_size * 3).map(|i| i as u8).collect();
+    let start = data.as_ptr() as u64;
+    let range = start..start + data.len() as u64;
+
+    let covered =
+        |plan: &TransferPlan| -> usize { plan.segments.iter().map(|&(_, len)| len).sum() };
+
+    for max_bytes in [None, Some(page_size as u64 + 5), Some(3)] {
+        if let Some(max_bytes) = max_bytes {
+            io.set_max_bytes_per_call(NonZeroU64::new(max_bytes).unwrap());
+        }
+
+        let plan = io.plan_transfer(range.clone()).unwrap();
+        let mut buffer = vec![0_u8; data.len()];
+        let count = io.read_at(start, &mut buffer).unwrap();
+        assert_eq!(covered(&plan), count);
+        assert_eq!(plan.size_of_not_covered_suffix, (data.len() - count) as u64);
+        assert_eq!(buffer[..count], data[..count]);
+
+        io.seek(SeekFrom::Start(start)).unwrap();
+        let report = io.read_vectored_report(&mut [IoSliceMut::new(&mut buffer)]);
+        assert_eq!(
+            report.unwrap().coverage_limited,
+            plan.size_of_not_covered_suffix != 0
+        );
+    }
+}
+
+#[test]
+fn write_if_equal_compares_before_writing() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+
+    let mut target = Box::new(*b"0123456789");
+    let address = target.as_mut_ptr() as u64;
+
+    assert!(!io.write_if_equal(address, b"01234x", b"abcdef").unwrap());
+    assert_eq!(&*target, b"0123456789");
+
+    assert!(io.write_if_equal(address + 2, b"2345", b"wxyz").unwrap());
+    assert_eq!(&*target, b"01wxyz6789");
+    assert_eq!(io.address, Some(0));
+
+    assert!(io.write_if_equal(0, b"0", b"1").is_err());
+}
+
+#[test]
+fn read_small_stays_inline() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+
+    let data: Vec<u8> = (0..64).collect();
+    let address = data.as_ptr() as u64;
+
+    let small = io.read_small::<16>(address + 4, 16).unwrap();
+    assert!(!small.spilled());
+    assert_eq!(small.as_slice(), &data[4..20]);
+
+    let large = io.read_small::<16>(address, 64).unwrap();
+    assert!(large.spilled());
+    assert_eq!(large.as_slice(), data.as_slice());
+
+    assert!(io.read_small::<16>(address, 0).unwrap().is_empty());
+    assert!(io.read_small::<16>(0, 1).is_err());
+}
+
+#[test]
+fn read_pointers_of_both_widths() {
+    let process_id = std::process::id();
+
+    let planted: [u8; 16] = [
+        0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, //
+        0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08,
+    ];
+    let address = planted.as_ptr() as u64;
+
+    let mut io = unsafe {
+        ProcessVirtualMemoryIO::builder(process_id)
+            .pointer_width(PointerWidth::Bits32)
+            .endianness(Endianness::Big)
+            .build()
+    }
+    .unwrap();
+    assert_eq!(io.read_pointer(address).unwrap(), 0x1234_5678);
+    assert_eq!(
+        io.read_pointers(address, 4).unwrap(),
+        [0x1234_5678, 0x9abc_def0, 0x0102_0304, 0x0506_0708]
+    );
+
+    io.set_endianness(Endianness::Little);
+    assert_eq!(io.read_pointer(address).unwrap(), 0x7856_3412);
+
+    io.set_pointer_width(PointerWidth::Bits64);
+    assert_eq!(
+        io.read_pointers(address, 2).unwrap(),
+        [0xf0de_bc9a_7856_3412, 0x0807_0605_0403_0201]
+    );
+
+    io.set_endianness(Endianness::Big);
+    assert_eq!(io.read_pointer(address + 8).unwrap(), 0x0102_0304_0506_0708);
+    assert!(io.read_pointers(address, usize::MAX).is_err());
+}
+
+#[test]
+fn read_integers_at_current_address() {
+    let process_id = std::process::id();
+    let planted: Box<[u8]> = Box::new([
+        0x12, 0x34, 0x56, 0x78, 0x9a, 0xbc, 0xde, 0xf0, //
+        0xff, 0xfe, 0x80, 0x00, 0x00, 0x00, 0x00, 0x01,
+    ]);
+    let address = planted.as_ptr() as u64;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, address) }.unwrap();
+    assert_eq!(io.read_u16(Endianness::Big).unwrap(), 0x1234);
+    assert_eq!(io.read_u16(Endianness::Little).unwrap(), 0x7856);
+    assert_eq!(io.read_u32(Endianness::Big).unwrap(), 0x9abc_def0);
+    assert_eq!(io.read_i16(Endianness::Big).unwrap(), -2);
+    assert_eq!(io.read_i16(Endianness::Little).unwrap(), 0x0080);
+    assert_eq!(io.address(), Some(address + 12));
+    assert_eq!(io.read_i32(Endianness::Little).unwrap(), 0x0100_0000);
+
+    io.set_address(address);
+    assert_eq!(
+        io.read_u64(Endianness::Little).unwrap(),
+        0xf0de_bc9a_7856_3412
+    );
+    assert_eq!(
+        io.read_i64(Endianness::Big).unwrap(),
+        -0x0001_7fff_ffff_ffff
+    );
+
+    io.set_address(0);
+    let err = io.read_u32(Endianness::native()).unwrap_err();
+    assert_matches!(err.kind(), ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn open_fds_of_current_process() {
+    let process_id = std::process::id();
+    let io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+
+    let file = File::open("/proc/self/maps").unwrap();
+    let fd = file.as_raw_fd() as u32;
+
+    let fds = io.open_fds().unwrap();
+    assert!(fds.windows(2).all(|pair| pair[0].0 < pair[1].0));
+    assert_matches!(
+        fds.iter().find(|(n, _)| *n == fd),
+        Some((_, path)) if path.ends_with("maps")
+    );
+}
+
+#[test]
+fn page_budget_limits_transfers() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    let data: Vec<u8> = (0..page_size * 4).map(|i| i as u8).collect();
+    let start = data.as_ptr() as usize;
+    let address = start.div_ceil(page_size) * page_size + 16;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, address as u64) }.unwrap();
+    io.set_page_budget(NonZeroUsize::new(2).unwrap());
+
+    let mut buf = vec![0_u8; page_size * 2];
+    let count = io.read(&mut buf).unwrap();
+    assert_eq!(count, page_size * 2 - 16);
+    assert_eq!(buf[..count], data[address - start..address - start + count]);
+
+    let count = io.read(&mut buf[..16]).unwrap();
+    assert_eq!(count, 16);
+    assert_eq!(io.address, Some((address + page_size * 2) as u64));
+}
+
+#[test]
+fn auto_continue_reads_beyond_page_budget() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    let data: Vec<u8> = (0..4 << 20).map(|i| (i % 251) as u8).collect();
+    let address = data.as_ptr() as u64;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, address) }.unwrap();
+    io.set_page_budget(NonZeroUsize::new(3).unwrap());
+
+    let mut buf = vec![0_u8; data.len()];
+    let count = io.read_at(address, &mut buf).unwrap();
+    assert!(count <= page_size * 3);
+
+    io.set_auto_continue(true);
+    buf.fill(0);
+    assert_eq!(io.read_at(address, &mut buf).unwrap(), data.len());
+    assert_eq!(buf, data);
+    assert_eq!(io.address(), Some(address));
+
+    buf.fill(0);
+    assert_eq!(io.read(&mut buf).unwrap(), data.len());
+    assert_eq!(buf, data);
+    assert_eq!(io.address(), Some(address + data.len() as u64));
+}
+
+#[test]
+fn auto_continue_reads_stop_before_unmapped_page() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    // Map eight pages, then unmap the last one.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 8,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    assert_eq!(
+        unsafe { libc::munmap(mapping.cast::<u8>().add(page_size * 7).cast(), page_size) },
+        0
+    );
+    let address = mapping as u64 + 16;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, address) }.unwrap();
+    io.set_page_budget(NonZeroUsize::new(2).unwrap());
+    io.set_auto_continue(true);
+
+    let mut buf = vec![0_u8; page_size * 8];
+    assert_eq!(io.read(&mut buf).unwrap(), page_size * 7 - 16);
+    assert_eq!(
+        io.read_raw(&mut buf).unwrap_err().os_error_code(),
+        Some(libc::EFAULT)
+    );
+
+    unsafe { libc::munmap(mapping, page_size * 7) };
+}
+
+#[test]
+fn memory_regions_in_sets() {
+    let process_id = std::process::id();
+    let io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+
+    let regions = io.memory_maps().unwrap();
+    let mut set: std::collections::BTreeSet<_> = regions.iter().cloned().collect();
+    set.extend(regions.iter().rev().cloned());
+    assert_eq!(set.len(), regions.len());
+    assert!(set.iter().zip(&regions).all(|(a, b)| a == b));
+
+    let low = MemoryRegion {
+        start: 0x1000,
+        end: 0x3000,
+        perms: Permissions::default(),
+        offset: 0,
+        dev: (0, 0),
+        inode: 0,
+        pathname: None,
+    };
+    let high = MemoryRegion {
+        start: 0x2000,
+        ..low.clone()
+    };
+    assert!(low < high);
+    assert!(
+        low < MemoryRegion {
+            end: 0x4000,
+            ..low.clone()
+        }
+    );
+
+    let hashed: HashSet<_> = [low.clone(), low, high].into_iter().collect();
+    assert_eq!(hashed.len(), 2);
+}
+
+#[test]
+fn diff_memory_maps() {
+    let old = procfs::parse_memory_maps(
+        "00400000-00452000 r-xp 00000000 08:02 173521 /usr/bin/a\n\
+         00e03000-00e24000 rw-p 00000000 00:00 0 [heap]\n\
+         7f0000000000-7f0000001000 rw-p 00000000 00:00 0\n",
+    )
+    .unwrap();
+    let new = procfs::parse_memory_maps(
+        "00400000-00452000 r-xp 00000000 08:02 173521 /usr/bin/a\n\
+         00e03000-00e24000 r-xp 00000000 00:00 0 [heap]\n\
+         7e0000000000-7e0000002000 r-xp 00000000 08:02 42 /usr/lib/b.so\n",
+    )
+    .unwrap();
+
+    assert!(diff_maps(&old, &old).is_empty());
+
+    let diff = diff_maps(&old, &new);
+    assert_eq!(diff.added, [new[2].clone()]);
+    assert_eq!(diff.removed, [old[2].clone()]);
+    assert_eq!(diff.changed, [(old[1].clone(), new[1].clone())]);
+}
+
+#[test]
+fn prefetch_current_process() {
+    let process_id = std::process::id();
+    let io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+
+    let data = vec![0_u8; 4 * *MIN_SYSTEM_PAGE_SIZE as usize];
+    let start = data.as_ptr() as u64;
+
+    match io.prefetch(start + 1..start + data.len() as u64 - 1) {
+        Ok(()) => {}
+        Err(err) => assert_matches!(
+            err.kind(),
+            ErrorKind::Io { error, .. }
+                if matches!(error.kind(), io::ErrorKind::Unsupported | io::ErrorKind::PermissionDenied)
+        ),
+    }
+
+    assert!(io.prefetch(start..start).is_ok());
+    assert!(io.prefetch(start + 1..start).is_err());
+}
+
+#[test]
+fn dump_region_zero_filled_fills_holes() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    // Map three pages, then unmap the middle one to create a hole.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 3,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let bytes = unsafe { slice::from_raw_parts_mut(mapping as *mut u8, page_size * 3) };
+    bytes.fill(0xa5);
+    assert_eq!(
+        unsafe { libc::munmap(mapping.cast::<u8>().add(page_size).cast(), page_size) },
+        0
+    );
+
+    let start = mapping as u64 + 8;
+    let end = start + (page_size * 3 - 16) as u64;
+    let data = io.dump_region_zero_filled(start..end).unwrap();
+
+    assert_eq!(data.len(), page_size * 3 - 16);
+    assert!(data[..page_size - 8].iter().all(|&b| b == 0xa5));
+    assert!(data[page_size - 8..page_size * 2 - 8]
+        .iter()
+        .all(|&b| b == 0));
+    assert!(data[page_size * 2 - 8..].iter().all(|&b| b == 0xa5));
+
+    unsafe {
+        libc::munmap(mapping, page_size);
+        libc::munmap(mapping.cast::<u8>().add(page_size * 2).cast(), page_size);
+    }
+}
+
+#[test]
+fn error_categories() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+
+    assert_eq!(
+        io.read_small::<1>(0, 1).unwrap_err().error_category(),
+        ErrorCategory::Os(libc::EFAULT)
+    );
+    assert_eq!(
+        io.try_seek(SeekFrom::End(1)).unwrap_err().error_category(),
+        ErrorCategory::Logic
+    );
+    assert_eq!(
+        Error::from(ErrorKind::TooManyVMPages).error_category(),
+        ErrorCategory::Logic
+    );
+    assert_eq!(
+        Error::from(u8::try_from(256_u32).unwrap_err()).error_category(),
+        ErrorCategory::Cast
+    );
+}
+
+#[test]
+fn lines_straddling_pages() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    let mut text = vec![b'a'; page_size * 2 + 100];
+    text[10] = b'\n';
+    text[page_size + 5] = b'\r';
+    text[page_size + 6] = b'\n';
+    text[page_size + 7] = 0xff;
+    let start = text.as_ptr() as u64;
+
+    let lines: Vec<String> = io
+        .lines(start..start + text.len() as u64)
+        .collect::<Result<_>>()
+        .unwrap();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0], "a".repeat(10));
+    assert_eq!(lines[1], "a".repeat(page_size + 5 - 11));
+    assert!(lines[2].starts_with(char::REPLACEMENT_CHARACTER));
+    assert_eq!(lines[2].len(), 3 + text.len() - (page_size + 8));
+
+    assert_eq!(io.lines(start..start).count(), 0);
+    assert_eq!(
+        io.lines(start + 5..start + 11).next().unwrap().unwrap(),
+        "aaaaa"
+    );
+
+    let mut lines = io.lines(0..16);
+    assert!(lines.next().unwrap().is_err());
+    assert!(lines.next().is_none());
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn read_tls_by_offset() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+
+    let block: [u64; 4] = [1, 0x1122_3344_5566_7788, 3, 4];
+    let tls_base = block.as_ptr() as u64 + 16;
+
+    assert_eq!(
+        io.read_tls::<u64>(tls_base, -8).unwrap(),
+        0x1122_3344_5566_7788
+    );
+    assert_eq!(io.read_tls::<[u32; 2]>(tls_base, 8).unwrap(), [4, 0]);
+    assert!(io.read_tls::<u8>(0, -1).is_err());
+}
+
+#[test]
+fn freeze_stops_and_continues() {
+    let child_id = unsafe { libc::fork() };
+    assert_ne!(child_id, -1);
+    if child_id == 0 {
+        loop {
+            unsafe { libc::pause() };
+        }
+    }
+
+    let io = unsafe { ProcessVirtualMemoryIO::new(child_id as u32, 0) }.unwrap();
+    let wait_for = |liveness| {
+        for _ in 0..1000 {
+            if io.is_alive().unwrap() == liveness {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        io.is_alive().unwrap()
+    };
+
+    let outer_guard = io.freeze().unwrap();
+    assert!(!outer_guard.was_already_stopped());
+    assert_eq!(io.is_alive().unwrap(), Liveness::Stopped);
+
+    let inner_guard = io.freeze().unwrap();
+    assert!(inner_guard.was_already_stopped());
+    drop(inner_guard);
+    assert_eq!(io.is_alive().unwrap(), Liveness::Stopped);
+
+    drop(outer_guard);
+    assert_eq!(wait_for(Liveness::Running), Liveness::Running);
+
+    unsafe {
+        libc::kill(child_id, libc::SIGKILL);
+        libc::waitpid(child_id, ptr::null_mut(), 0);
+    }
+
+    // Neither the current process, nor any of its threads, can be frozen.
+    let thread_id = unsafe { libc::gettid() } as u32;
+    for process_id in [std::process::id(), thread_id] {
+        let io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+        assert_matches!(
+            io.freeze().unwrap_err().kind(),
+            ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::InvalidInput
+        );
+    }
+}
+
+#[test]
+fn os_errors_classification() {
+    for (code, kind) in [
+        (libc::EACCES, io::ErrorKind::PermissionDenied),
+        (libc::EPERM, io::ErrorKind::PermissionDenied),
+        (libc::ESRCH, io::ErrorKind::NotFound),
+        (libc::EINVAL, io::ErrorKind::InvalidInput),
+        (libc::ENOSYS, io::ErrorKind::Unsupported),
+    ] {
+        let err = Error::from_io3(io::Error::from_raw_os_error(code), "test", 1);
+        assert_matches!(err.kind(), ErrorKind::Io { error, .. } if error.kind() == kind);
+        assert_eq!(err.os_error_code(), Some(code));
+        assert_eq!(err.error_category(), ErrorCategory::Os(code));
+    }
+
+    let err = Error::from_io3(io::Error::from_raw_os_error(libc::EFAULT), "test", 1);
+    assert_matches!(
+        err.kind(),
+        ErrorKind::Io { error, .. } if error.raw_os_error() == Some(libc::EFAULT)
+    );
+}
+
+#[test]
+fn new_reaped_process() {
+    let child_id = unsafe { libc::fork() };
+    assert_ne!(child_id, -1);
+    if child_id == 0 {
+        unsafe { libc::_exit(0) };
+    }
+    assert_eq!(
+        unsafe { libc::waitpid(child_id, ptr::null_mut(), 0) },
+        child_id
+    );
+
+    let err = unsafe { ProcessVirtualMemoryIO::new(child_id as u32, 0) }.unwrap_err();
+    assert_matches!(
+        err.kind(),
+        ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::NotFound
+    );
+    assert_eq!(err.os_error_code(), Some(libc::ESRCH));
+}
+
+#[test]
+fn read_write_raw_errors() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let mut buf = [0u8; 1];
+
+    assert_eq!(
+        io.read_raw(&mut buf).unwrap_err().os_error_code(),
+        Some(libc::EFAULT)
+    );
+    assert_eq!(
+        io.write_raw(&buf).unwrap_err().os_error_code(),
+        Some(libc::EFAULT)
+    );
+
+    let mut target = Box::new(0x1234_u16);
+    io.seek(SeekFrom::Start(ptr::addr_of_mut!(*target) as u64))
+        .unwrap();
+    assert_eq!(io.read_raw(&mut buf).unwrap(), 1);
+    assert_eq!(buf, 0x1234_u16.to_ne_bytes()[..1]);
+    assert_eq!(io.write_raw(&[0xaa]).unwrap(), 1);
+    assert_eq!(target.to_ne_bytes()[1], 0xaa);
+}
+
+#[test]
+fn read_large_beyond_iov_max() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    io.set_page_budget(NonZeroUsize::new(3).unwrap());
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    let data: Vec<u8> = (0..page_size * 10 + 7).map(|i| (i % 251) as u8).collect();
+    let mut buf = vec![0_u8; data.len() - 3];
+    assert_eq!(
+        io.read_large(&mut buf, data.as_ptr() as u64 + 3).unwrap(),
+        buf.len()
+    );
+    assert_eq!(buf, data[3..]);
+    assert_eq!(io.address, Some(0));
+
+    assert_eq!(io.read_large(&mut [], 0).unwrap(), 0);
+    assert_eq!(
+        io.read_large(&mut buf, 0).unwrap_err().os_error_code(),
+        Some(libc::EFAULT)
+    );
+}
+
+#[test]
+fn exact_positioned_transfers_beyond_page_budget() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    io.set_page_budget(NonZeroUsize::new(2).unwrap());
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    let data: Vec<u8> = (0..page_size * 5 + 7).map(|i| (i % 251) as u8).collect();
+    let mut buf = vec![0_u8; data.len() - 3];
+    io.read_exact_at(data.as_ptr() as u64 + 3, &mut buf)
+        .unwrap();
+    assert_eq!(buf, data[3..]);
+
+    let mut target = vec![0_u8; data.len()];
+    io.write_all_at(target.as_mut_ptr() as u64 + 3, &buf)
+        .unwrap();
+    target = std::hint::black_box(target);
+    assert_eq!(target[3..], data[3..]);
+    assert_eq!(io.address, Some(0));
+
+    io.read_exact_at(0, &mut []).unwrap();
+    io.write_all_at(0, &[]).unwrap();
+    assert_eq!(
+        io.read_exact_at(0, &mut buf).unwrap_err().os_error_code(),
+        Some(libc::EFAULT)
+    );
+    assert_eq!(
+        io.write_all_at(0, &buf).unwrap_err().os_error_code(),
+        Some(libc::EFAULT)
+    );
+}
+
+#[test]
+fn write_large_beyond_iov_max() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    let data: Vec<u8> = (0..page_size * (*SYSTEM_IOV_MAX * 2 + 1))
+        .map(|i| (i % 251) as u8)
+        .collect();
+    let mut target = vec![0_u8; data.len() + 3];
+    assert_eq!(
+        io.write_large(&data, target.as_mut_ptr() as u64 + 3)
+            .unwrap(),
+        data.len()
+    );
+    target = std::hint::black_box(target);
+    assert_eq!(target[3..], data);
+    assert_eq!(io.address, Some(0));
+
+    // Writing stops at the first page that cannot be written.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 2,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let hole = unsafe { mapping.cast::<u8>().add(page_size) };
+    assert_eq!(unsafe { libc::munmap(hole.cast(), page_size) }, 0);
+
+    assert_eq!(
+        io.write_large(&data[..page_size], hole as u64 - 8).unwrap(),
+        8
+    );
+    assert_eq!(
+        io.write_large(&data[..8], hole as u64)
+            .unwrap_err()
+            .os_error_code(),
+        Some(libc::EFAULT)
+    );
+    assert_eq!(io.write_large(&[], 0).unwrap(), 0);
+    unsafe { libc::munmap(mapping, page_size) };
+}
+
+#[cfg(feature = "bytes")]
+#[test]
+fn read_into_bytes() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+
+    let data: Vec<u8> = (0..100).collect();
+    let bytes = io.read_bytes(data.as_ptr() as u64 + 10, 50).unwrap();
+    assert_eq!(bytes, data[10..60]);
+    assert_eq!(bytes.slice(5..10), data[15..20]);
+
+    assert!(io.read_bytes(0, 1).is_err());
+}
+
+#[test]
+fn builder_skips_liveness_check() {
+    let child_id = unsafe { libc::fork() };
+    assert_ne!(child_id, -1);
+    if child_id == 0 {
+        unsafe { libc::_exit(0) };
+    }
+    assert_eq!(
+        unsafe { libc::waitpid(child_id, ptr::null_mut(), 0) },
+        child_id
+    );
+
+    let builder = ProcessVirtualMemoryIO::builder(child_id as u32);
+    assert!(unsafe { builder.clone().build() }.is_err());
+
+    let mut io = unsafe { builder.skip_liveness_on_new(true).build() }.unwrap();
+    assert_eq!(
+        io.read_raw(&mut [0u8; 1]).unwrap_err().os_error_code(),
+        Some(libc::ESRCH)
+    );
+
+    let value = 42_u64;
+    let mut io = unsafe {
+        ProcessVirtualMemoryIO::builder(std::process::id())
+            .initial_address(ptr::addr_of!(value) as u64)
+            .build()
+    }
+    .unwrap();
+    assert_eq!(io.stream_position().unwrap(), ptr::addr_of!(value) as u64);
+    for pid in -2_i32..=0 {
+        let builder = ProcessVirtualMemoryIO::builder(pid as u32).skip_liveness_on_new(true);
+        assert!(unsafe { builder.build() }.is_err());
+    }
+}
+
+#[test]
+fn builder_behavioral_toggles() {
+    let process_id = std::process::id();
+    let data = b"toggles".to_vec();
+    let address = data.as_ptr() as u64;
+
+    let io = unsafe { ProcessVirtualMemoryIO::builder(process_id).build() }.unwrap();
+    assert!(!io.auto_continue);
+    assert!(!io.check_writability);
+    assert_eq!(io.backend(), Backend::Syscall);
+
+    let mut io = unsafe {
+        ProcessVirtualMemoryIO::builder(process_id)
+            .initial_address(address)
+            .auto_continue(true)
+            .check_writability(true)
+            .backend(Backend::ProcMem)
+            .build()
+    }
+    .unwrap();
+    assert!(io.auto_continue);
+    assert!(io.check_writability);
+    assert_eq!(io.backend(), Backend::ProcMem);
+
+    let mut buf = [0_u8; 7];
+    io.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"toggles");
+    assert_eq!(io.last_backend(), Some(Backend::ProcMem));
+}
+
+#[test]
+fn adopt_proc_mem_file() {
+    use std::os::unix::fs::FileExt;
+
+    let process_id = std::process::id();
+    let mut data = b"adopted".to_vec();
+    let address = data.as_mut_ptr() as u64;
+
+    let io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    assert!(io.into_proc_mem_file().is_none());
+
+    // A file open for reading only allows reads.
+    let file = File::open(format!("/proc/{process_id}/mem")).unwrap();
+    let mut io =
+        unsafe { ProcessVirtualMemoryIO::from_proc_mem_file(file, process_id, address) }.unwrap();
+    assert_eq!(io.backend(), Backend::ProcMem);
+
+    let mut buf = [0_u8; 7];
+    io.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"adopted");
+    assert_eq!(io.last_backend(), Some(Backend::ProcMem));
+    assert_eq!(
+        io.write_at(address, b"written")
+            .unwrap_err()
+            .os_error_code(),
+        Some(libc::EBADF)
+    );
+    assert_eq!(&data, b"adopted");
+
+    // The file is handed back as is.
+    let file = io.into_proc_mem_file().unwrap();
+    let mut buf = [0_u8; 7];
+    file.read_exact_at(&mut buf, address).unwrap();
+    assert_eq!(&buf, b"adopted");
+
+    let file = File::open(format!("/proc/{process_id}/mem")).unwrap();
+    assert_matches!(
+        unsafe { ProcessVirtualMemoryIO::from_proc_mem_file(file, 0, 0) }.unwrap_err().kind(),
+        ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+fn new_unchecked_current_process() {
+    let value = 0x1234_5678_u32;
+    let address = ptr::addr_of!(value) as u64;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new_unchecked(std::process::id(), address) };
+
+    let mut buf = [0_u8; 4];
+    io.read_exact(&mut buf).unwrap();
+    assert_eq!(u32::from_ne_bytes(buf), value);
+    assert_eq!(io.stream_position().unwrap(), address + 4);
+}
+
+#[test]
+fn memory_summary_parsing() {
+    let rollup = procfs::parse_memory_summary(
+        "00400000-7fff6080d000 ---p 00000000 00:00 0       [rollup]\n\
+         Rss:                1392 kB\n\
+         Pss:                 398 kB\n\
+         Pss_Dirty:           104 kB\n\
+         Shared_Clean:       1248 kB\n\
+         Shared_Dirty:          0 kB\n\
+         Private_Clean:        40 kB\n\
+         Private_Dirty:       104 kB\n\
+         Swap:                  8 kB\n",
+    )
+    .unwrap();
+    assert_eq!(rollup.rss, 1392 * 1024);
+    assert_eq!(rollup.pss, 398 * 1024);
+    assert_eq!(rollup.shared_clean, 1248 * 1024);
+    assert_eq!(rollup.private_dirty, 104 * 1024);
+    assert_eq!(rollup.swap, 8 * 1024);
+
+    let smaps = procfs::parse_memory_summary(
+        "00400000-00452000 r-xp 00000000 08:02 173521      /usr/bin/dbus-daemon\n\
+         Rss:                  12 kB\n\
+         VmFlags: rd ex mr mw me dw\n\
+         00e03000-00e24000 rw-p 00000000 00:00 0           [heap]\n\
+         Rss:                   4 kB\n",
+    )
+    .unwrap();
+    assert_eq!(smaps.rss, 16 * 1024);
+
+    assert!(procfs::parse_memory_summary("Rss: many kB\n").is_none());
+
+    let io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), 0) }.unwrap();
+    let summary = io.memory_summary().unwrap();
+    assert_ne!(summary.rss, 0);
+    assert!(summary.private_clean + summary.private_dirty <= summary.rss);
+}
+
+#[test]
+fn read_exact_vectored_fills_all_buffers() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 3,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let bytes = unsafe { slice::from_raw_parts_mut(mapping as *mut u8, page_size * 3) };
+    bytes.iter_mut().enumerate().for_each(|(i, b)| *b = i as u8);
+
+    // Buffers span page boundaries, and each transfer covers at most one page.
+    let start = mapping as u64 + 8;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, start) }.unwrap();
+    io.set_page_budget(NonZeroUsize::new(1).unwrap());
+
+    let mut first = vec![0_u8; page_size];
+    let mut empty = [0_u8; 0];
+    let mut second = vec![0_u8; page_size + 8];
+    io.read_exact_vectored(&mut [
+        IoSliceMut::new(&mut first),
+        IoSliceMut::new(&mut empty),
+        IoSliceMut::new(&mut second),
+    ])
+    .unwrap();
+    assert_eq!(first, bytes[8..page_size + 8]);
+    assert_eq!(second, bytes[page_size + 8..page_size * 2 + 16]);
+    assert_eq!(io.address, Some(start + (page_size * 2 + 8) as u64));
+
+    // Unmap the last page to force a partial transfer.
+    assert_eq!(
+        unsafe { libc::munmap(mapping.cast::<u8>().add(page_size * 2).cast(), page_size) },
+        0
+    );
+    io.seek(SeekFrom::Start(start)).unwrap();
+    let mut buf = vec![0_u8; page_size * 2];
+    let err = io
+        .read_exact_vectored(&mut [IoSliceMut::new(&mut first), IoSliceMut::new(&mut buf)])
+        .unwrap_err();
+    assert_matches!(
+        err.kind(),
+        ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::UnexpectedEof
+    );
+
+    unsafe { libc::munmap(mapping, page_size * 2) };
+}
+
+#[test]
+fn system_limits() {
+    let page_size = system_page_size().unwrap().get();
+    assert_eq!(page_size, *MIN_SYSTEM_PAGE_SIZE);
+    assert!(page_size.is_power_of_two());
+    assert_eq!(system_iov_max().get(), *SYSTEM_IOV_MAX);
+
+    // Limits are resolved once per instance.
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), 0) }.unwrap();
+    assert_eq!(io.page_size, page_size);
+    assert_eq!(io.iov_max, system_iov_max());
+    assert_eq!(io.max_iov_count(), *SYSTEM_IOV_MAX);
+    io.set_page_budget(NonZeroUsize::new(2).unwrap());
+    assert_eq!(io.max_iov_count(), 2);
+}
+
+#[test]
+fn recommended_buf_size_follows_page_budget() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+
+    assert_eq!(
+        io.recommended_buf_size().unwrap(),
+        page_size * *SYSTEM_IOV_MAX
+    );
+
+    io.set_page_budget(NonZeroUsize::new(2).unwrap());
+    let size = io.recommended_buf_size().unwrap();
+    assert_eq!(size, page_size * 2);
+
+    let data: Vec<u8> = (0..size * 2).map(|i| i as u8).collect();
+    let address = (data.as_ptr() as usize).div_ceil(page_size) * page_size;
+    io.seek(SeekFrom::Start(address as u64)).unwrap();
+
+    let mut reader = io::BufReader::with_capacity(size, io);
+    let mut buf = vec![0_u8; 16];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(reader.buffer().len(), size - 16);
+}
+
+#[test]
+fn read_aligned_words() {
+    let value = 0x0102_0304_0506_0708_u64;
+    let address = ptr::addr_of!(value) as u64;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), 0) }.unwrap();
+    assert_eq!(io.read_word_aligned(address).unwrap(), value);
+    assert_matches!(
+        io.read_word_aligned(address + 4).unwrap_err().kind(),
+        ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::InvalidInput
+    );
+
+    // The current process cannot be frozen around the read.
+    assert_matches!(
+        io.read_word_consistent(address).unwrap_err().kind(),
+        ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::InvalidInput
+    );
+
+    // The child process has a copy of `value` at the same address.
+    let child_id = unsafe { libc::fork() };
+    assert_ne!(child_id, -1);
+    if child_id == 0 {
+        loop {
+            unsafe { libc::pause() };
+        }
+    }
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(child_id as u32, 0) }.unwrap();
+    assert_eq!(io.read_word_consistent(address).unwrap(), value);
+    assert_ne!(io.is_alive().unwrap(), Liveness::Dead);
+
+    unsafe {
+        libc::kill(child_id, libc::SIGKILL);
+        libc::waitpid(child_id, ptr::null_mut(), 0);
+    }
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn scan_value_in_range() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    io.set_page_budget(NonZeroUsize::new(1).unwrap());
+
+    let needle = 0xdead_beef_u32;
+    let mut data = vec![0_u32; page_size];
+    data[3] = needle;
+    data[page_size / 2] = needle;
+    let start = data.as_ptr() as u64;
+    let end = start + (page_size * 4) as u64;
+
+    // Plant an unaligned value straddling a page boundary.
+    let bytes: &mut [u8] = bytemuck::cast_slice_mut(&mut data);
+    let boundary = (start as usize).div_ceil(page_size) * page_size - start as usize;
+    let straddling = boundary + page_size - 2;
+    bytes[straddling..straddling + 4].copy_from_slice(&needle.to_ne_bytes());
+
+    let aligned = io.scan_value(start..end, &needle, true).unwrap();
+    assert_eq!(aligned, [start + 12, start + (page_size * 2) as u64]);
+
+    let unaligned = io.scan_value(start..end, &needle, false).unwrap();
+    assert_eq!(
+        unaligned,
+        [
+            start + 12,
+            start + straddling as u64,
+            start + (page_size * 2) as u64
+        ]
+    );
+
+    assert!(io.scan_value(end..start, &needle, true).is_err());
+
+    // Values straddling adjacent memory regions are found as well.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 2,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let bytes = unsafe { slice::from_raw_parts_mut(mapping as *mut u8, page_size * 2) };
+    bytes[page_size - 2..page_size + 2].copy_from_slice(&needle.to_ne_bytes());
+    let second_page = unsafe { mapping.cast::<u8>().add(page_size) };
+    assert_eq!(
+        unsafe { libc::mprotect(second_page.cast(), page_size, libc::PROT_READ) },
+        0
+    );
+    let start = mapping as u64;
+    let end = start + (page_size * 2) as u64;
+    assert_eq!(
+        io.region_containing(start).unwrap().unwrap().end,
+        start + page_size as u64
+    );
+    assert_eq!(
+        io.scan_value(start..end, &needle, false).unwrap(),
+        [start + page_size as u64 - 2]
+    );
+
+    unsafe { libc::munmap(mapping, page_size * 2) };
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn filter_addresses_by_new_value() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    io.set_page_budget(NonZeroUsize::new(2).unwrap());
+
+    let mut data = [7_u16; 8];
+    let start = data.as_ptr() as u64;
+    let candidates = io.scan_value(start..start + 16, &7_u16, true).unwrap();
+    assert_eq!(candidates.len(), 8);
+
+    data[1] = 9;
+    data[4] = 9;
+    data[6] = 9;
+    std::hint::black_box(&data);
+    let mut with_unreadable = candidates.clone();
+    with_unreadable.insert(3, 0);
+    assert_eq!(
+        io.filter_addresses(&with_unreadable, &9_u16).unwrap(),
+        [start + 2, start + 8, start + 12]
+    );
+    assert_eq!(io.filter_addresses(&candidates, &7_u16).unwrap().len(), 5);
+    assert!(io.filter_addresses(&[], &7_u16).unwrap().is_empty());
+}
+
+#[test]
+fn signal_state_of_processes() {
+    let state = procfs::parse_signal_state(
+        "Name:\tcat\n\
+         SigQ:\t1/24002\n\
+         SigPnd:\t0000000000000100\n\
+         ShdPnd:\t0000000000000000\n\
+         SigBlk:\t0000000000010000\n\
+         SigIgn:\t0000000000001000\n\
+         SigCgt:\t8000000000000400\n",
+    )
+    .unwrap();
+    assert_eq!((state.queued, state.queue_limit), (1, 24002));
+    assert!(state.pending.contains(libc::SIGKILL));
+    assert!(state.blocked.contains(libc::SIGCHLD));
+    assert!(state.ignored.contains(libc::SIGPIPE));
+    assert!(state.caught.contains(libc::SIGSEGV));
+    assert!(state.caught.contains(64));
+    assert!(!state.caught.contains(0) && !state.caught.contains(65));
+
+    assert!(procfs::parse_signal_state("SigQ:\t1/24002\n").is_none());
+
+    // A child process ignoring `SIGUSR2`.
+    let child_id = unsafe { libc::fork() };
+    assert_ne!(child_id, -1);
+    if child_id == 0 {
+        unsafe { libc::signal(libc::SIGUSR2, libc::SIG_IGN) };
+        loop {
+            unsafe { libc::pause() };
+        }
+    }
+
+    let io = unsafe { ProcessVirtualMemoryIO::new(child_id as u32, 0) }.unwrap();
+    let mut ignored = false;
+    for _ in 0..1000 {
+        ignored = io.signal_state().unwrap().ignored.contains(libc::SIGUSR2);
+        if ignored {
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(1));
+    }
+    assert!(ignored);
+
+    unsafe {
+        libc::kill(child_id, libc::SIGKILL);
+        libc::waitpid(child_id, ptr::null_mut(), 0);
+    }
+}
+
+#[test]
+fn walk_linked_lists() {
+    #[repr(C)]
+    struct Node {
+        value: u32,
+        next: *const Node,
+    }
+
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let next_offset = std::mem::offset_of!(Node, next);
+
+    let mut nodes: Vec<Node> = (0..4)
+        .map(|value| Node {
+            value,
+            next: ptr::null(),
+        })
+        .collect();
+    let addresses: Vec<u64> = nodes
+        .iter()
+        .map(|node| ptr::from_ref(node) as u64)
+        .collect();
+    for i in 0..3 {
+        nodes[i].next = addresses[i + 1] as *const Node;
+    }
+    std::hint::black_box(&nodes);
+
+    assert_eq!(
+        io.walk_list(addresses[0], next_offset, 10).unwrap(),
+        addresses
+    );
+    assert_eq!(
+        io.walk_list(addresses[1], next_offset, 2).unwrap(),
+        addresses[1..3]
+    );
+    assert!(io.walk_list(0, next_offset, 10).unwrap().is_empty());
+
+    // A cycle back to the second node.
+    nodes[3].next = addresses[1] as *const Node;
+    std::hint::black_box(&nodes);
+    assert_eq!(
+        io.walk_list(addresses[0], next_offset, 10).unwrap(),
+        addresses
+    );
+    assert_eq!(nodes.iter().map(|node| node.value).sum::<u32>(), 6);
+
+    // A dangling pointer.
+    nodes[3].next = ptr::dangling();
+    std::hint::black_box(&nodes);
+    assert!(io.walk_list(addresses[0], next_offset, 10).is_err());
+}
+
+#[test]
+fn wait_channel_and_sched_stats() {
+    assert_eq!(procfs::parse_wait_channel("0"), None);
+    assert_eq!(procfs::parse_wait_channel(""), None);
+    assert_eq!(
+        procfs::parse_wait_channel("do_sys_poll").as_deref(),
+        Some("do_sys_poll")
+    );
+
+    let stats = procfs::parse_sched_stats("1015837 29581 12\n").unwrap();
+    assert_eq!(stats.run_time_ns, 1_015_837);
+    assert_eq!(stats.wait_time_ns, 29_581);
+    assert_eq!(stats.timeslices, 12);
+    assert!(procfs::parse_sched_stats("1015837 29581\n").is_none());
+
+    let io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), 0) }.unwrap();
+    io.wait_channel().unwrap();
+    match io.sched_stats() {
+        Ok(stats) => assert_ne!(stats.run_time_ns, 0),
+        Err(err) => assert_matches!(
+            err.kind(),
+            ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::NotFound
+        ),
+    }
+}
+
+#[test]
+fn try_from_process_id_and_path() {
+    let child_id = unsafe { libc::fork() };
+    assert_ne!(child_id, -1);
+    if child_id == 0 {
+        loop {
+            unsafe { libc::pause() };
+        }
+    }
+
+    let io = ProcessVirtualMemoryIO::try_from(child_id as u32).unwrap();
+    assert_eq!(io.process_id(), child_id as u32);
+    assert_eq!(io.address, Some(0));
+
+    let path = PathBuf::from(format!("/proc/{child_id}"));
+    let io = ProcessVirtualMemoryIO::try_from(path.as_path()).unwrap();
+    assert_eq!(io.process_id(), child_id as u32);
+
+    unsafe {
+        libc::kill(child_id, libc::SIGKILL);
+        libc::waitpid(child_id, ptr::null_mut(), 0);
+    }
+
+    for path in ["/proc/self", "/proc/12/mem", "/tmp/12", "/proc/-1"] {
+        assert_matches!(
+            ProcessVirtualMemoryIO::try_from(Path::new(path)).unwrap_err().kind(),
+            ErrorKind::Io { error, process_id: None, .. } if error.kind() == io::ErrorKind::InvalidInput
+        );
+    }
+
+    // Processes sharing the virtual memory of the current process are refused.
+    extern "C" fn pause_forever(_argument: *mut c_void) -> libc::c_int {
+        loop {
+            unsafe { libc::pause() };
+        }
+    }
+    let mut stack = vec![0_u128; 4096];
+    let stack_top = unsafe { stack.as_mut_ptr().add(stack.len()) };
+    let clone_id = unsafe {
+        libc::clone(
+            pause_forever,
+            stack_top.cast(),
+            libc::CLONE_VM | libc::SIGCHLD,
+            ptr::null_mut(),
+        )
+    };
+    assert_ne!(clone_id, -1);
+
+    let thread_id = unsafe { libc::gettid() } as u32;
+    for process_id in [std::process::id(), thread_id, clone_id as u32] {
+        assert_matches!(
+            ProcessVirtualMemoryIO::try_from(process_id).unwrap_err().kind(),
+            ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::InvalidInput
+        );
+    }
+    assert_matches!(
+        ProcessVirtualMemoryIO::try_from(Path::new(&format!("/proc/{clone_id}")))
+            .unwrap_err()
+            .kind(),
+        ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::InvalidInput
+    );
+
+    unsafe {
+        libc::kill(clone_id, libc::SIGKILL);
+        libc::waitpid(clone_id, ptr::null_mut(), 0);
+    }
+}
+
+#[test]
+fn new_at_module_start() {
+    let process_id = std::process::id();
+    let io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let executable = std::env::current_exe().unwrap();
+    let executable = executable.to_str().unwrap();
+
+    let expected = io
+        .memory_maps()
+        .unwrap()
+        .into_iter()
+        .find(|region| region.pathname.as_deref() == Some(executable))
+        .unwrap();
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new_at_module(process_id, executable) }.unwrap();
+    assert_eq!(io.stream_position().unwrap(), expected.start);
+
+    let mut magic = [0_u8; 4];
+    io.read_exact(&mut magic).unwrap();
+    assert_eq!(&magic, b"\x7fELF");
+
+    assert_matches!(
+        unsafe { ProcessVirtualMemoryIO::new_at_module(process_id, "no such module") }
+            .unwrap_err()
+            .kind(),
+        ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::NotFound
+    );
+}
+
+#[test]
+fn read_skips_holes_when_enabled() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    // Map three pages, then unmap the middle one to create a hole.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 3,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let bytes = unsafe { slice::from_raw_parts_mut(mapping as *mut u8, page_size * 3) };
+    bytes.fill(0xa5);
+    let start = mapping as u64;
+
+    // Cache the memory regions before the hole exists.
+    let mut stale_io = unsafe {
+        ProcessVirtualMemoryIO::builder(process_id)
+            .initial_address(start)
+            .auto_skip_holes(page_size as u64)
+            .initial_maps_cache(true)
+            .build()
+    }
+    .unwrap();
+
+    assert_eq!(
+        unsafe { libc::munmap(mapping.cast::<u8>().add(page_size).cast(), page_size) },
+        0
+    );
+    let mut buf = vec![0_u8; page_size * 3];
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, start) }.unwrap();
+    assert_eq!(io.read(&mut buf).unwrap(), page_size);
+    assert!(io.read(&mut buf).is_err());
+    assert_eq!(io.skipped_bytes(), 0);
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, start) }.unwrap();
+    io.set_auto_skip_holes(Some(page_size as u64 - 1));
+    assert_eq!(io.read(&mut buf).unwrap(), page_size);
+    assert!(io.read(&mut buf).is_err());
+    assert_eq!(io.skipped_bytes(), 0);
+
+    let mut io = unsafe {
+        ProcessVirtualMemoryIO::builder(process_id)
+            .initial_address(start)
+            .auto_skip_holes(page_size as u64)
+            .build()
+    }
+    .unwrap();
+    assert_eq!(io.read(&mut buf).unwrap(), page_size);
+    assert_eq!(io.read(&mut buf[..16]).unwrap(), 16);
+    assert_eq!(io.skipped_bytes(), page_size as u64);
+    assert_eq!(io.address, Some(start + (page_size * 2 + 16) as u64));
+    assert!(buf[..16].iter().all(|&b| b == 0xa5));
+
+    // A stale cache of memory regions is refreshed to find the next one.
+    assert_eq!(stale_io.read(&mut buf).unwrap(), page_size);
+    assert_eq!(stale_io.read(&mut buf[..16]).unwrap(), 16);
+    assert_eq!(stale_io.skipped_bytes(), page_size as u64);
+
+    unsafe {
+        libc::munmap(mapping, page_size);
+        libc::munmap(mapping.cast::<u8>().add(page_size * 2).cast(), page_size);
+    }
+}
+
+#[test]
+fn trace_hook_observes_transfers() {
+    use std::sync::{Arc, Mutex};
+
+    let process_id = std::process::id();
+    let data = [0x5a_u8; 64];
+    let address = data.as_ptr() as u64;
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, address) }.unwrap();
+    let sink = Arc::clone(&events);
+    io.set_trace_hook(Box::new(move |event| sink.lock().unwrap().push(event)));
+
+    let mut buf = [0_u8; 16];
+    io.read_exact(&mut buf).unwrap();
+    io.seek(SeekFrom::Start(address)).unwrap();
+    io.write_all(&buf).unwrap();
+    io.seek(SeekFrom::Start(0)).unwrap();
+    assert!(io.read(&mut buf).is_err());
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 3);
+    assert_eq!(events[0].operation, "process_vm_readv");
+    assert_eq!(events[0].process_id, process_id);
+    assert_eq!(events[0].address, address);
+    assert_eq!(events[0].requested_bytes, 16);
+    assert_eq!(events[0].transferred_bytes, 16);
+    assert_eq!(events[0].errno, None);
+    assert_eq!(events[1].operation, "process_vm_writev");
+    assert_eq!(events[1].transferred_bytes, 16);
+    assert_eq!(events[2].address, 0);
+    assert_eq!(events[2].transferred_bytes, 0);
+    assert_eq!(events[2].errno, Some(libc::EFAULT));
+}
+
+#[test]
+fn trace_hook_observes_scattered_transfers() {
+    use std::sync::{Arc, Mutex};
+
+    let process_id = std::process::id();
+    let data: Vec<u8> = (0..64).collect();
+    let address = data.as_ptr() as u64;
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let sink = Arc::clone(&events);
+    io.set_trace_hook(Box::new(move |event| sink.lock().unwrap().push(event)));
+
+    let remote = [(address + 4, 8), (address + 32, 8)];
+    let mut buf = [0_u8; 16];
+    assert_eq!(
+        io.read_scattered(&remote, &mut [IoSliceMut::new(&mut buf)])
+            .unwrap(),
+        16
+    );
+    assert!(io
+        .read_scattered(&[(0, 8)], &mut [IoSliceMut::new(&mut buf)])
+        .is_err());
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].operation, "process_vm_readv");
+    assert_eq!(events[0].address, address + 4);
+    assert_eq!(events[0].requested_bytes, 16);
+    assert_eq!(events[0].transferred_bytes, 16);
+    assert_eq!(events[0].errno, None);
+    assert_eq!(events[1].address, 0);
+    assert_eq!(events[1].errno, Some(libc::EFAULT));
+
+    #[cfg(feature = "metrics")]
+    assert_eq!(io.transfer_histogram()[4], 1);
+}
+
+#[test]
+fn short_writes_stop_at_page_boundaries() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    // Map three pages, then make the last one read-only.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 3,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let bytes = unsafe { slice::from_raw_parts(mapping as *const u8, page_size * 3) };
+    let read_only = unsafe { mapping.cast::<u8>().add(page_size * 2) };
+    assert_eq!(
+        unsafe { libc::mprotect(read_only.cast(), page_size, libc::PROT_READ) },
+        0
+    );
+
+    // The transfer is split into a first partial page, an inner page, and a
+    // last partial page, which cannot be written.
+    let start = mapping as u64 + 8;
+    let data = vec![0xa5_u8; page_size * 3 - 16];
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, start) }.unwrap();
+
+    let count = io.write(&data).unwrap();
+    assert_eq!(count, page_size * 2 - 8);
+    assert_eq!(io.address, Some(read_only as u64));
+    assert!(bytes[8..page_size * 2].iter().all(|&b| b == 0xa5));
+    assert!(bytes[page_size * 2..].iter().all(|&b| b == 0));
+
+    assert_eq!(
+        io.write_raw(&data[count..]).unwrap_err().os_error_code(),
+        Some(libc::EFAULT)
+    );
+    assert_eq!(io.address, Some(read_only as u64));
+
+    // Only the first partial page can be written.
+    io.seek(SeekFrom::Start(read_only as u64 - 8)).unwrap();
+    assert_eq!(io.write(&data[..16]).unwrap(), 8);
+    assert_eq!(io.address, Some(read_only as u64));
+
+    unsafe { libc::munmap(mapping, page_size * 3) };
+}
+
+#[test]
+fn cached_region_containing() {
+    let process_id = std::process::id();
+    let value = 42_u64;
+    let address = ptr::addr_of!(value) as u64;
+
+    let mut io = unsafe {
+        ProcessVirtualMemoryIO::builder(process_id)
+            .initial_maps_cache(true)
+            .build()
+    }
+    .unwrap();
+    assert!(io.maps_cache.is_some());
+
+    let region = io.region_containing(address).unwrap().unwrap();
+    assert!(region.start <= address && address < region.end);
+    assert!(region.perms.read && region.perms.write);
+    assert!(io.region_containing(0).unwrap().is_none());
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    assert!(io.maps_cache.is_none());
+    assert!(io.region_containing(address).unwrap().is_some());
+    assert!(io.maps_cache.is_some());
+
+    // New mappings are seen only after refreshing the cache.
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size,
+            libc::PROT_READ,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    io.refresh_maps().unwrap();
+    assert!(io.region_containing(mapping as u64).unwrap().is_some());
+
+    unsafe { libc::munmap(mapping, page_size) };
+}
+
+#[test]
+fn read_with_region_metadata() {
+    let process_id = std::process::id();
+    let data = [0x5a_u8; 16];
+    let address = data.as_ptr() as u64;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, address) }.unwrap();
+    let mut buf = [0_u8; 16];
+    let (count, region) = io.read_with_region(&mut buf).unwrap();
+    assert_eq!(count, 16);
+    assert_eq!(buf, data);
+    let region = region.unwrap();
+    assert!(region.start <= address && address < region.end);
+    assert!(!region.perms.execute);
+
+    let code_address = read_with_region_metadata as *const () as u64;
+    io.seek(SeekFrom::Start(code_address)).unwrap();
+    let (count, region) = io.read_with_region(&mut buf).unwrap();
+    assert_eq!(count, 16);
+    assert!(region.unwrap().perms.execute);
+
+    io.seek(SeekFrom::Start(0)).unwrap();
+    assert!(io.read_with_region(&mut buf).is_err());
+}
+
+#[test]
+fn range_to_extent_conversion() {
+    assert_eq!(range_to_extent(0x1000..0x3000).unwrap(), (0x1000, 0x2000));
+    assert_eq!(range_to_extent(5..5).unwrap(), (5, 0));
+    assert_eq!(range_to_extent(0..u64::MAX).unwrap(), (0, u64::MAX));
+
+    let (start, end) = (2, 1);
+    assert_matches!(
+        range_to_extent(start..end).unwrap_err().kind(),
+        ErrorKind::Io { error, process_id: None, .. } if error.kind() == io::ErrorKind::InvalidInput
+    );
+
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    assert_matches!(
+        io.dump_region_zero_filled(start..end).unwrap_err().kind(),
+        ErrorKind::Io { error, process_id: Some(_), .. } if error.kind() == io::ErrorKind::InvalidInput
+    );
+}
+
+#[test]
+fn validate_write_without_writing() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    // Map three pages: writable, read-only, then unmapped.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 3,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let start = mapping as u64;
+    unsafe {
+        assert_eq!(
+            libc::mprotect(
+                mapping.cast::<u8>().add(page_size).cast(),
+                page_size,
+                libc::PROT_READ
+            ),
+            0
+        );
+        assert_eq!(
+            libc::munmap(mapping.cast::<u8>().add(page_size * 2).cast(), page_size),
+            0
+        );
+    }
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    io.validate_write(start, page_size).unwrap();
+    io.validate_write(start + 8, 0).unwrap();
+
+    assert_matches!(
+        io.validate_write(start + 8, page_size).unwrap_err().kind(),
+        ErrorKind::NotWritable { address, .. } if *address == start + page_size as u64
+    );
+    assert_eq!(
+        io.validate_write(start + (page_size * 2) as u64, 1)
+            .unwrap_err()
+            .os_error_code(),
+        Some(libc::EFAULT)
+    );
+    assert!(io.validate_write(u64::MAX, 2).is_err());
+
+    unsafe { libc::munmap(mapping, page_size * 2) };
+}
+
+#[test]
+fn check_writability_before_writing() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    // Map two pages: writable, then read-only.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 2,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let start = mapping as u64;
+    let read_only = start + page_size as u64;
+    let second_page = unsafe { mapping.cast::<u8>().add(page_size) };
+    assert_eq!(
+        unsafe { libc::mprotect(second_page.cast(), page_size, libc::PROT_READ) },
+        0
+    );
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, read_only) }.unwrap();
+    let data = [0x5a_u8; 8];
+
+    // Without checking, the system reports an opaque fault.
+    assert_eq!(
+        io.write_at(read_only, &data).unwrap_err().os_error_code(),
+        Some(libc::EFAULT)
+    );
+
+    io.set_check_writability(true);
+    assert_matches!(
+        io.write_at(read_only, &data).unwrap_err().kind(),
+        ErrorKind::NotWritable { address, .. } if *address == read_only
+    );
+    assert_matches!(
+        io.write_all_at(read_only, &data).unwrap_err().kind(),
+        ErrorKind::NotWritable { .. }
+    );
+    assert_eq!(
+        io.write(&data).unwrap_err().kind(),
+        io::ErrorKind::PermissionDenied
+    );
+    assert_eq!(io.address(), Some(read_only));
+    assert_eq!(io.write_at(read_only, &[]).unwrap(), 0);
+
+    // A write starting in a writable region is issued.
+    assert_eq!(io.write_at(start, &data).unwrap(), data.len());
+    assert_eq!(unsafe { *mapping.cast::<u8>() }, 0x5a);
+
+    // Checks use cached regions until they are refreshed.
+    assert_eq!(
+        unsafe {
+            libc::mprotect(
+                second_page.cast(),
+                page_size,
+                libc::PROT_READ | libc::PROT_WRITE,
+            )
+        },
+        0
+    );
+    assert!(io.write_at(read_only, &data).is_err());
+    io.refresh_maps().unwrap();
+    assert_eq!(io.write_at(read_only, &data).unwrap(), data.len());
+    assert_eq!(unsafe { *second_page }, 0x5a);
+
+    unsafe { libc::munmap(mapping, page_size * 2) };
+}
+
+#[test]
+fn write_if_equal_checks_writability() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size,
+            libc::PROT_READ,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let read_only = mapping as u64;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    assert_eq!(
+        io.write_if_equal(read_only, &[0; 4], &[1; 4])
+            .unwrap_err()
+            .os_error_code(),
+        Some(libc::EFAULT)
+    );
+
+    io.set_check_writability(true);
+    assert_matches!(
+        io.write_if_equal(read_only, &[0; 4], &[1; 4])
+            .unwrap_err()
+            .kind(),
+        ErrorKind::NotWritable { address, .. } if *address == read_only
+    );
+    // Nothing is written when the data differs, so nothing is checked.
+    assert!(!io.write_if_equal(read_only, &[1; 4], &[2; 4]).unwrap());
+    assert_eq!(unsafe { *mapping.cast::<[u8; 4]>() }, [0; 4]);
+
+    unsafe { libc::munmap(mapping, page_size) };
+}
+
+#[test]
+fn readable_regions_with_readers() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 2,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let bytes = unsafe { slice::from_raw_parts_mut(mapping as *mut u8, page_size * 2) };
+    bytes.iter_mut().enumerate().for_each(|(i, b)| *b = i as u8);
+    let start = mapping as u64;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let mut found = false;
+    for (region, mut reader) in io.readable_regions().unwrap() {
+        assert!(region.perms.read);
+        assert_eq!(reader.remaining(), region.start..region.end);
+        if region.start <= start && start < region.end {
+            let mut data = Vec::new();
+            reader.read_to_end(&mut data).unwrap();
+            assert_eq!(data.len() as u64, region.end - region.start);
+            let offset = (start - region.start) as usize;
+            assert_eq!(data[offset..offset + page_size * 2], *bytes);
+            assert!(reader.remaining().is_empty());
+            found = true;
+        }
+    }
+    assert!(found);
+
+    unsafe { libc::munmap(mapping, page_size * 2) };
+}
+
+#[test]
+fn dump_region_to_writer() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    // Map three pages, the last of which is a guard page.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 3,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let bytes = unsafe { slice::from_raw_parts_mut(mapping as *mut u8, page_size * 2) };
+    bytes
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, b)| *b = (i % 251) as u8);
+    assert_eq!(
+        unsafe {
+            libc::mprotect(
+                mapping.cast::<u8>().add(page_size * 2).cast(),
+                page_size,
+                libc::PROT_NONE,
+            )
+        },
+        0
+    );
+    let start = mapping as u64;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let mut region = io.region_containing(start).unwrap().unwrap().clone();
+    region.start = start;
+    region.end = start + (page_size * 2) as u64;
+
+    let mut out = Vec::new();
+    assert_eq!(
+        io.dump_region(&region, &mut out).unwrap(),
+        (page_size * 2) as u64
+    );
+    assert_eq!(out, *bytes);
+    assert_eq!(io.address(), Some(region.end));
+
+    // Dumping stops at the guard page, after copying the data before it.
+    region.end = start + (page_size * 3) as u64;
+    let mut out = Vec::new();
+    let err = io.dump_region(&region, &mut out).unwrap_err();
+    assert_eq!(err.os_error_code(), Some(libc::EFAULT));
+    assert_eq!(out, *bytes);
+    assert_eq!(io.address(), Some(start + (page_size * 2) as u64));
+
+    // Pseudo-regions that readable_regions() skips are not read either.
+    let vvar = io
+        .memory_maps()
+        .unwrap()
+        .into_iter()
+        .find(|region| region.pathname.as_deref() == Some("[vvar]"));
+    if let Some(vvar) = vvar {
+        let mut out = Vec::new();
+        let err = io.dump_region(&vvar, &mut out).unwrap_err();
+        assert_eq!(err.os_error_code(), Some(libc::EFAULT));
+        assert!(out.is_empty());
+        assert_eq!(io.address(), Some(vvar.start));
+    }
+
+    unsafe { libc::munmap(mapping, page_size * 3) };
+}
+
+#[test]
+fn read_all_into_sparse_image() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    // Map three pages, then unmap the middle one to create a hole.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 3,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let bytes = unsafe { slice::from_raw_parts_mut(mapping as *mut u8, page_size * 3) };
+    bytes
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, b)| *b = (i % 251) as u8);
+    assert_eq!(
+        unsafe { libc::munmap(mapping.cast::<u8>().add(page_size).cast(), page_size) },
+        0
+    );
+
+    let start = mapping as u64 + 8;
+    let end = start + (page_size * 3 - 16) as u64;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let image = io.read_all_into(start..end).unwrap();
+
+    assert_eq!(image.data.len(), page_size * 2 - 16);
+    assert_eq!(
+        image.segments,
+        [
+            (start, 0..page_size - 8),
+            (
+                mapping as u64 + (page_size * 2) as u64,
+                page_size - 8..page_size * 2 - 16
+            ),
+        ]
+    );
+    assert_eq!(image.data[..page_size - 8], bytes[8..page_size]);
+    assert_eq!(
+        image.data[page_size - 8..],
+        bytes[page_size * 2..page_size * 3 - 8]
+    );
+
+    assert_eq!(image.address_of(0), Some(start));
+    assert_eq!(
+        image.address_of(page_size - 8),
+        Some(mapping as u64 + (page_size * 2) as u64)
+    );
+    assert_eq!(image.address_of(image.data.len()), None);
+    assert_eq!(SparseImage::default().address_of(0), None);
+
+    unsafe {
+        libc::munmap(mapping, page_size);
+        libc::munmap(mapping.cast::<u8>().add(page_size * 2).cast(), page_size);
+    }
+}
+
+#[test]
+fn transfers_split_at_target_page_size() {
+    let process_id = std::process::id();
+    let host_page_size = *MIN_SYSTEM_PAGE_SIZE;
+
+    let io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    assert_eq!(io.target_page_size(), host_page_size);
+
+    let page_size = host_page_size / 4;
+    let mut io = unsafe {
+        ProcessVirtualMemoryIO::builder(process_id)
+            .target_page_size(NonZeroU64::new(page_size).unwrap())
+            .build()
+    }
+    .unwrap();
+    assert_eq!(io.target_page_size(), page_size);
+    assert_eq!(
+        io.recommended_buf_size().unwrap(),
+        page_size as usize * *SYSTEM_IOV_MAX
+    );
+
+    let start = 0x1000_0000 + 8;
+    let max_iov_count = *SYSTEM_IOV_MAX as u64;
+    let plan = io
+        .plan_transfer(start..start + page_size * (max_iov_count + 1))
+        .unwrap();
+    assert_eq!(
+        plan.segments[..3],
+        [
+            (start, page_size as usize - 8),
+            (start - 8 + page_size, page_size as usize),
+            (start - 8 + page_size * 2, page_size as usize),
+        ]
+    );
+
+    // Reading across target page boundaries still works.
+    let data: Vec<u8> = (0..host_page_size * 2).map(|i| i as u8).collect();
+    let mut buffer = vec![0_u8; data.len() - 16];
+    io.seek(SeekFrom::Start(data[8..].as_ptr() as u64)).unwrap();
+    io.read_exact(&mut buffer).unwrap();
+    assert_eq!(buffer, data[8..data.len() - 8]);
+
+    for page_size in [3, host_page_size + 1] {
+        let builder = ProcessVirtualMemoryIO::builder(process_id)
+            .target_page_size(NonZeroU64::new(page_size).unwrap());
+        assert_matches!(
+            unsafe { builder.build() }.unwrap_err().kind(),
+            ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::InvalidInput
+        );
+    }
+}
+
+#[test]
+fn generic_io_through_mutable_reference() {
+    fn read_at<R: Read + Seek>(mut reader: R, address: u64, buf: &mut [u8]) -> io::Result<()> {
+        reader.seek(SeekFrom::Start(address))?;
+        reader.read_exact(buf)
+    }
+
+    fn write_at<W: Write + Seek>(mut writer: W, address: u64, buf: &[u8]) -> io::Result<()> {
+        writer.seek(SeekFrom::Start(address))?;
+        writer.write_all(buf)
+    }
+
+    let mut data = [1_u8, 2, 3, 4];
+    let address = data.as_ptr() as u64;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), 0) }.unwrap();
+
+    let mut buf = [0_u8; 4];
+    read_at(&mut io, address, &mut buf).unwrap();
+    assert_eq!(buf, data);
+
+    write_at(&mut io, address, &[5, 6]).unwrap();
+    data = std::hint::black_box(data);
+    assert_eq!(data, [5, 6, 3, 4]);
+
+    // The object is still usable, including its inherent methods.
+    assert_eq!(io.stream_position().unwrap(), address + 2);
+    assert_eq!(
+        io.read_small::<2>(address + 2, 2).unwrap().as_slice(),
+        [3, 4]
+    );
+}
+
+#[test]
+fn read_vdso_and_skip_pseudo_regions() {
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), 0) }.unwrap();
+    let maps = io.memory_maps().unwrap();
+
+    match maps
+        .iter()
+        .find(|region| region.pathname.as_deref() == Some("[vdso]"))
+    {
+        Some(region) => {
+            let vdso = io.read_vdso().unwrap();
+            assert_eq!(vdso.len() as u64, region.end - region.start);
+            assert_eq!(vdso[..4], *b"\x7fELF");
+        }
+        None => assert_matches!(
+            io.read_vdso().unwrap_err().kind(),
+            ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::NotFound
+        ),
+    }
+
+    for (region, _data) in io.read_regions_in(0..u64::MAX).unwrap() {
+        assert!(!region
+            .pathname
+            .as_deref()
+            .is_some_and(|pathname| UNREADABLE_PSEUDO_REGIONS.contains(&pathname)));
+    }
+}
+
+#[test]
+fn read_exact_at_into_reuses_capacity() {
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+    let data: Vec<u8> = (0..page_size * 3).map(|i| i as u8).collect();
+    let address = data.as_ptr() as u64;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), 0) }.unwrap();
+
+    let mut out = Vec::with_capacity(page_size + 16);
+    out.extend_from_slice(&[0xff; 4]);
+    let capacity = out.capacity();
+    for offset in [0, 8, page_size - 8] {
+        io.read_exact_at_into(&mut out, page_size + 16, address + offset as u64)
+            .unwrap();
+        assert_eq!(out, data[offset..offset + page_size + 16]);
+        assert_eq!(out.capacity(), capacity);
+    }
+    assert_eq!(io.stream_position().unwrap(), 0);
+
+    io.read_exact_at_into(&mut out, 0, address).unwrap();
+    assert!(out.is_empty());
+
+    // Reading across an unmapped page fails.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 2,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let hole = unsafe { mapping.cast::<u8>().add(page_size) };
+    assert_eq!(unsafe { libc::munmap(hole.cast(), page_size) }, 0);
+
+    assert!(io
+        .read_exact_at_into(&mut out, 16, hole as u64 - 8)
+        .is_err());
+    assert!(out.is_empty());
+    unsafe { libc::munmap(mapping, page_size) };
+}
+
+#[test]
+fn read_checkpoint_image() {
+    use std::fs;
+
+    fn varint(mut value: u64, out: &mut Vec<u8>) {
+        while value >= 0x80 {
+            out.push((value as u8) | 0x80);
+            value >>= 7;
+        }
+        out.push(value as u8);
+    }
+
+    fn message(fields: &[(u64, u64)], out: &mut Vec<u8>) {
+        let mut encoded = Vec::new();
+        for &(field_number, value) in fields {
+            varint(field_number << 3, &mut encoded);
+            varint(value, &mut encoded);
+        }
+        out.extend_from_slice(&(encoded.len() as u32).to_ne_bytes());
+        out.extend_from_slice(&encoded);
+    }
+
+    let page_size = *MIN_SYSTEM_PAGE_SIZE;
+    let directory =
+        std::env::temp_dir().join(format!("process_vm_io-checkpoint-{}", std::process::id()));
+    fs::create_dir_all(&directory).unwrap();
+
+    let mut pagemap = Vec::new();
+    pagemap.extend_from_slice(&0x5456_4319_u32.to_ne_bytes());
+    pagemap.extend_from_slice(&0x5608_4025_u32.to_ne_bytes());
+    message(&[(1, 7)], &mut pagemap);
+    message(&[(1, 0x7f00_0000), (2, 2), (4, 4)], &mut pagemap); // Present.
+    message(&[(1, 0x7f10_0000), (2, 1), (4, 2)], &mut pagemap); // Lazy.
+    message(&[(1, 0x7f20_0000), (2, 1), (3, 1)], &mut pagemap); // In parent.
+    message(&[(1, 0x7f30_0000), (2, 1)], &mut pagemap); // Present.
+    fs::write(directory.join("pagemap-1234.img"), &pagemap).unwrap();
+
+    let pages: Vec<u8> = (0..page_size * 3).map(|i| (i % 251) as u8).collect();
+    fs::write(directory.join("pages-7.img"), &pages).unwrap();
+
+    let mut image = CheckpointImage::open(&directory, 1234).unwrap();
+    assert_eq!(image.process_id(), 1234);
+    assert_eq!(
+        image.present_ranges().collect::<Vec<_>>(),
+        [
+            0x7f00_0000..0x7f00_0000 + page_size * 2,
+            0x7f30_0000..0x7f30_0000 + page_size,
+        ]
+    );
+
+    // Reading stops at the end of a stored address range.
+    let mut buf = vec![0_u8; 32];
+    image
+        .seek(SeekFrom::Start(0x7f00_0000 + page_size * 2 - 16))
+        .unwrap();
+    assert_eq!(image.read(&mut buf).unwrap(), 16);
+    assert_eq!(
+        buf[..16],
+        pages[(page_size * 2 - 16) as usize..(page_size * 2) as usize]
+    );
+
+    image.set_address(0x7f30_0000 + 8);
+    image.read_exact(&mut buf).unwrap();
+    assert_eq!(
+        buf,
+        pages[(page_size * 2 + 8) as usize..(page_size * 2 + 40) as usize]
+    );
+    assert_eq!(image.stream_position().unwrap(), 0x7f30_0000 + 40);
+
+    // Pages that are not stored in the checkpoint read like unmapped memory.
+    for address in [0, 0x7f10_0000, 0x7f20_0000] {
+        image.seek(SeekFrom::Start(address)).unwrap();
+        assert_eq!(
+            image.read_raw(&mut buf).unwrap_err().os_error_code(),
+            Some(libc::EFAULT)
+        );
+    }
+
+    // Truncated pages are rejected.
+    fs::write(directory.join("pages-7.img"), &pages[..page_size as usize]).unwrap();
+    assert!(CheckpointImage::open(&directory, 1234).is_err());
+    assert!(CheckpointImage::open(&directory, 1235).is_err());
+
+    fs::remove_dir_all(&directory).unwrap();
+}
+
+#[test]
+fn scattered_transfers_of_discontiguous_ranges() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    let data: Vec<u8> = (0..page_size * 4).map(|i| (i % 251) as u8).collect();
+    let address = data.as_ptr() as u64;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+
+    let remote = [
+        (address + 10, 5),
+        (address + page_size as u64 * 3, 20),
+        (address, 0),
+        (address + 100, 7),
+    ];
+    let expected: Vec<u8> = remote
+        .iter()
+        .flat_map(|&(a, len)| &data[(a - address) as usize..][..len])
+        .copied()
+        .collect();
+
+    let (mut a, mut b) = ([0_u8; 12], [0_u8; 20]);
+    assert_eq!(
+        io.read_scattered(
+            &remote,
+            &mut [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)]
+        )
+        .unwrap(),
+        expected.len()
+    );
+    assert_eq!([&a[..], &b].concat(), expected);
+    assert_eq!(io.address(), Some(0));
+
+    // More address ranges than `IOV_MAX`, read into fewer buffers.
+    let remote: Vec<(u64, usize)> = (0..system_iov_max().get() * 2 + 3)
+        .map(|i| (address + (i * 7 % (page_size * 4 - 3)) as u64, 3))
+        .collect();
+    let expected: Vec<u8> = remote
+        .iter()
+        .flat_map(|&(a, len)| &data[(a - address) as usize..][..len])
+        .copied()
+        .collect();
+    let mut buf = vec![0_u8; expected.len()];
+    let (first, second) = buf.split_at_mut(1000);
+    assert_eq!(
+        io.read_scattered(
+            &remote,
+            &mut [IoSliceMut::new(first), IoSliceMut::new(second)]
+        )
+        .unwrap(),
+        expected.len()
+    );
+    assert_eq!(buf, expected);
+
+    // Gather the data back, into a zeroed copy.
+    let mut target = vec![0_u8; data.len()];
+    let target_address = target.as_mut_ptr() as u64;
+    let target_remote: Vec<(u64, usize)> = remote
+        .iter()
+        .map(|&(a, len)| (a - address + target_address, len))
+        .collect();
+    assert_eq!(
+        io.write_scattered(&target_remote, &[IoSlice::new(&buf)])
+            .unwrap(),
+        buf.len()
+    );
+    for &(a, len) in &remote {
+        let offset = (a - address) as usize;
+        assert_eq!(target[offset..offset + len], data[offset..offset + len]);
+    }
+
+    // Reading stops at the first address range that cannot be read.
+    let remote = [(address, 8), (0, 8), (address + 8, 8)];
+    let mut buf = [0_u8; 24];
+    assert_eq!(
+        io.read_scattered(&remote, &mut [IoSliceMut::new(&mut buf)])
+            .unwrap(),
+        8
+    );
+    assert_eq!(
+        io.read_scattered(&remote[1..], &mut [IoSliceMut::new(&mut buf)])
+            .unwrap_err()
+            .os_error_code(),
+        Some(libc::EFAULT)
+    );
+    assert_eq!(
+        io.read_scattered(&[], &mut [IoSliceMut::new(&mut buf)])
+            .unwrap(),
+        0
+    );
+}
+
+#[test]
+fn copy_between_processes() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    let data: Vec<u8> = (0..page_size * 100 + 7).map(|i| (i % 251) as u8).collect();
+    let mut target = vec![0_u8; data.len()];
+    let (from, to) = (data.as_ptr() as u64, target.as_mut_ptr() as u64);
+
+    let mut src = unsafe { ProcessVirtualMemoryIO::new(process_id, from) }.unwrap();
+    let mut dst = unsafe { ProcessVirtualMemoryIO::new(process_id, to) }.unwrap();
+    assert_eq!(
+        copy_between(&mut src, &mut dst, data.len() as u64).unwrap(),
+        data.len() as u64
+    );
+    assert_eq!(target, data);
+    assert_eq!(src.address(), Some(from + data.len() as u64));
+    assert_eq!(dst.address(), Some(to + data.len() as u64));
+
+    // Copying stops at the first page that cannot be written.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 2,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    assert_eq!(
+        unsafe { libc::munmap(mapping.cast::<u8>().add(page_size).cast(), page_size) },
+        0
+    );
+    let to = mapping as u64 + 16;
+    src.set_address(from);
+    dst.set_address(to);
+    let count = copy_between(&mut src, &mut dst, data.len() as u64).unwrap();
+    assert_eq!(count, (page_size - 16) as u64);
+    assert_eq!(src.address(), Some(from + count));
+    assert_eq!(dst.address(), Some(to + count));
+    let copied = unsafe { slice::from_raw_parts(to as *const u8, count as usize) };
+    assert_eq!(copied, &data[..count as usize]);
+
+    assert_eq!(
+        copy_between(&mut src, &mut dst, 1)
+            .unwrap_err()
+            .os_error_code(),
+        Some(libc::EFAULT)
+    );
+    assert_eq!(src.address(), Some(from + count));
+
+    unsafe { libc::munmap(mapping, page_size) };
+}
+
+#[test]
+fn fill_and_zero_regions() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    let len = page_size * (*SYSTEM_IOV_MAX * 2 + 1) + 5;
+    let mut target = vec![0_u8; len + 6];
+    assert_eq!(
+        io.fill(target.as_mut_ptr() as u64 + 3, 0xa5, len as u64)
+            .unwrap(),
+        len as u64
+    );
+    target = std::hint::black_box(target);
+    assert_eq!(target[..3], [0; 3]);
+    assert!(target[3..len + 3].iter().all(|&byte| byte == 0xa5));
+    assert_eq!(target[len + 3..], [0; 3]);
+    assert_eq!(io.address, Some(0));
+
+    assert_eq!(
+        io.zero(target.as_mut_ptr() as u64 + 1, page_size as u64)
+            .unwrap(),
+        page_size as u64
+    );
+    target = std::hint::black_box(target);
+    assert!(target[..page_size + 1].iter().all(|&byte| byte == 0));
+    assert_eq!(target[page_size + 1], 0xa5);
+
+    // Filling stops at the first page that cannot be written.
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 2,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let hole = unsafe { mapping.cast::<u8>().add(page_size) };
+    assert_eq!(unsafe { libc::munmap(hole.cast(), page_size) }, 0);
+
+    assert_eq!(io.fill(hole as u64 - 8, 0x3c, page_size as u64).unwrap(), 8);
+    let filled = unsafe { slice::from_raw_parts(hole.sub(9), 9) };
+    assert_eq!(filled, [0, 0x3c, 0x3c, 0x3c, 0x3c, 0x3c, 0x3c, 0x3c, 0x3c]);
+    assert_eq!(
+        io.zero(hole as u64, 8).unwrap_err().os_error_code(),
+        Some(libc::EFAULT)
+    );
+    assert_eq!(io.fill(0, 0x3c, 0).unwrap(), 0);
+
+    unsafe { libc::munmap(mapping, page_size) };
+}
+
+#[test]
+fn empty_vectored_transfers_issue_no_system_call() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let data = [0x5a_u8; 16];
+    let address = data.as_ptr() as u64;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), address) }.unwrap();
+    let calls = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&calls);
+    io.set_trace_hook(Box::new(move |_event| {
+        counter.fetch_add(1, Ordering::Relaxed);
+    }));
+
+    let (mut a, mut b) = ([0_u8; 0], [0_u8; 0]);
+    assert_eq!(io.read_vectored(&mut []).unwrap(), 0);
+    assert_eq!(
+        io.read_vectored(&mut [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)])
+            .unwrap(),
+        0
+    );
+    io.read_exact_vectored(&mut []).unwrap();
+    io.read_exact_vectored(&mut [IoSliceMut::new(&mut a), IoSliceMut::new(&mut b)])
+        .unwrap();
+    assert_eq!(io.write_vectored(&[]).unwrap(), 0);
+    assert_eq!(
+        io.write_vectored(&[IoSlice::new(&[]), IoSlice::new(&[])])
+            .unwrap(),
+        0
+    );
+    assert_eq!(io.read(&mut []).unwrap(), 0);
+    assert_eq!(io.write(&[]).unwrap(), 0);
+
+    assert_eq!(calls.load(Ordering::Relaxed), 0);
+    assert_eq!(io.stream_position().unwrap(), address);
+
+    // Empty buffers mixed with non-empty ones are transferred normally.
+    let mut buf = [0_u8; 8];
+    assert_eq!(
+        io.read_vectored(&mut [IoSliceMut::new(&mut a), IoSliceMut::new(&mut buf)])
+            .unwrap(),
+        8
+    );
+    assert_eq!(buf, data[..8]);
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+}
+
+#[test]
+fn vectored_transfers_beyond_iov_max_local_buffers() {
+    let process_id = std::process::id();
+    let buffer_count = system_iov_max().get() * 2 + 100;
+    let sizes: Vec<usize> = (0..buffer_count).map(|i| i % 13).collect();
+    let total: usize = sizes.iter().sum();
+
+    let data: Vec<u8> = (0..total).map(|i| (i % 251) as u8).collect();
+    let address = data.as_ptr() as u64;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, address) }.unwrap();
+
+    let mut buffers: Vec<Vec<u8>> = sizes.iter().map(|&size| vec![0_u8; size]).collect();
+    let mut slices: Vec<IoSliceMut> = buffers.iter_mut().map(|b| IoSliceMut::new(b)).collect();
+    assert_eq!(io.read_vectored(&mut slices).unwrap(), total);
+    assert_eq!(buffers.concat(), data);
+    assert_eq!(io.address(), Some(address + total as u64));
+
+    let mut target = vec![0_u8; total];
+    io.set_address(target.as_mut_ptr() as u64);
+    let slices: Vec<IoSlice> = buffers.iter().map(|b| IoSlice::new(b)).collect();
+    assert_eq!(io.write_vectored(&slices).unwrap(), total);
+    assert_eq!(target, data);
+}
+
+#[test]
+fn exit_readiness_of_child_process() {
+    let child_id = unsafe { libc::fork() };
+    assert_ne!(child_id, -1);
+    if child_id == 0 {
+        loop {
+            unsafe { libc::pause() };
+        }
+    }
+
+    let io = unsafe { ProcessVirtualMemoryIO::new(child_id as u32, 0) }.unwrap();
+    let fd = io.process_fd().unwrap().as_raw_fd();
+    assert_eq!(io.process_fd().unwrap().as_raw_fd(), fd);
+    assert!(!io.exit_readiness().unwrap());
+
+    assert_eq!(unsafe { libc::kill(child_id, libc::SIGKILL) }, 0);
+    let mut poll_fd = libc::pollfd {
+        fd,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    assert_eq!(unsafe { libc::poll(&mut poll_fd, 1, 10_000) }, 1);
+    assert!(io.exit_readiness().unwrap());
+
+    assert_eq!(
+        unsafe { libc::waitpid(child_id, ptr::null_mut(), 0) },
+        child_id
+    );
+}
+
+#[test]
+fn pidfd_guards_transfers_against_process_exit() {
+    let data = b"pidfd".to_vec();
+    let address = data.as_ptr() as u64;
+
+    let child_id = unsafe { libc::fork() };
+    assert_ne!(child_id, -1);
+    if child_id == 0 {
+        loop {
+            unsafe { libc::pause() };
+        }
+    }
+
+    let mut io =
+        unsafe { ProcessVirtualMemoryIO::from_pid_with_pidfd(child_id as u32, address) }.unwrap();
+    assert_eq!(io.process_id(), child_id as u32);
+    let mut buf = [0_u8; 5];
+    io.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"pidfd");
+
+    // Another instance owns a duplicate of the `pidfd`.
+    let fd = io.process_fd().unwrap().as_raw_fd();
+    let mut other =
+        unsafe { ProcessVirtualMemoryIO::from_pidfd(io.process_fd().unwrap(), 0) }.unwrap();
+    assert_eq!(other.process_id(), child_id as u32);
+    assert_ne!(other.process_fd().unwrap().as_raw_fd(), fd);
+    assert_eq!(other.read_at(address, &mut buf).unwrap(), 5);
+
+    assert_eq!(unsafe { libc::kill(child_id, libc::SIGKILL) }, 0);
+    let mut poll_fd = libc::pollfd {
+        fd,
+        events: libc::POLLIN,
+        revents: 0,
+    };
+    assert_eq!(unsafe { libc::poll(&mut poll_fd, 1, 10_000) }, 1);
+
+    assert_eq!(
+        io.ensure_process_fd_alive().unwrap_err().raw_os_error(),
+        Some(libc::ESRCH)
+    );
+    assert_eq!(
+        other
+            .read_at(address, &mut buf)
+            .unwrap_err()
+            .os_error_code(),
+        Some(libc::ESRCH)
+    );
+    let err =
+        unsafe { ProcessVirtualMemoryIO::from_pidfd(io.process_fd().unwrap(), 0) }.unwrap_err();
+    assert_eq!(err.os_error_code(), Some(libc::ESRCH));
+
+    assert_eq!(
+        unsafe { libc::waitpid(child_id, ptr::null_mut(), 0) },
+        child_id
+    );
+
+    // File descriptors that do not refer to processes are rejected.
+    let file = File::open("/proc/self/maps").unwrap();
+    let err = unsafe { ProcessVirtualMemoryIO::from_pidfd(file.as_fd(), 0) }.unwrap_err();
+    assert_matches!(err.kind(), ErrorKind::Io { error, .. } if error.kind() == io::ErrorKind::InvalidInput);
+
+    assert_eq!(
+        procfs::parse_pidfd_process_id("pos:\t0\nflags:\t02000002\nPid:\t1234\nNSpid:\t1234\n"),
+        Some(1234)
+    );
+    assert_eq!(procfs::parse_pidfd_process_id("Pid:\t-1\n"), Some(-1));
+    assert_eq!(procfs::parse_pidfd_process_id("pos:\t0\n"), None);
+}
+
+#[test]
+fn revalidate_start_time_of_target_process() {
+    let process_id = std::process::id();
+    let data = b"revalidate".to_vec();
+    let address = data.as_ptr() as u64;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, address) }.unwrap();
+    let start_time = procfs::start_time(process_id as libc::pid_t).unwrap();
+    assert_eq!(io.start_time.get(), Some(&start_time));
+    io.revalidate().unwrap();
+
+    // Recorded by the first call when created without checks.
+    let io_unchecked = unsafe { ProcessVirtualMemoryIO::new_unchecked(process_id, address) };
+    assert!(io_unchecked.start_time.get().is_none());
+    io_unchecked.revalidate().unwrap();
+    assert_eq!(io_unchecked.start_time.get(), Some(&start_time));
+
+    // As if the process identifier had been reused.
+    io.start_time = OnceLock::from(start_time + 1);
+    let mut buf = [0_u8; 10];
+    assert_eq!(io.read(&mut buf).unwrap(), 10);
+    assert_eq!(
+        io.revalidate().unwrap_err().os_error_code(),
+        Some(libc::ESRCH)
+    );
+
+    io.set_auto_revalidate(Some(Duration::ZERO));
+    assert_eq!(
+        io.read_at(address, &mut buf).unwrap_err().os_error_code(),
+        Some(libc::ESRCH)
+    );
+
+    // Checked at most once per interval.
+    io.start_time = OnceLock::from(start_time);
+    io.set_auto_revalidate(Some(Duration::from_secs(3600)));
+    assert_eq!(io.read_at(address, &mut buf).unwrap(), 10);
+    io.start_time = OnceLock::from(start_time + 1);
+    assert_eq!(io.read_at(address, &mut buf).unwrap(), 10);
+    assert_eq!(&buf, b"revalidate");
+
+    // A process that exited fails revalidation.
+    let child_id = unsafe { libc::fork() };
+    assert_ne!(child_id, -1);
+    if child_id == 0 {
+        unsafe { libc::_exit(0) };
+    }
+    let child = unsafe { ProcessVirtualMemoryIO::new_unchecked(child_id as u32, 0) };
+    assert_eq!(
+        unsafe { libc::waitpid(child_id, ptr::null_mut(), 0) },
+        child_id
+    );
+    assert_eq!(
+        child.revalidate().unwrap_err().os_error_code(),
+        Some(libc::ESRCH)
+    );
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn transfer_histogram_buckets() {
+    let data = [0x5a_u8; 64];
+    let address = data.as_ptr() as u64;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), address) }.unwrap();
+    assert_eq!(io.transfer_histogram(), [0; 64]);
+
+    let mut buf = [0_u8; 64];
+    for size in [1, 2, 3, 4, 64] {
+        io.seek(SeekFrom::Start(address)).unwrap();
+        io.read_exact(&mut buf[..size]).unwrap();
+    }
+    io.seek(SeekFrom::Start(0)).unwrap();
+    assert!(io.read(&mut buf).is_err());
+
+    let mut expected = [0; 64];
+    expected[0] = 1;
+    expected[1] = 2;
+    expected[2] = 1;
+    expected[6] = 1;
+    assert_eq!(io.transfer_histogram(), expected);
+}
+
+#[test]
+fn address_distinguishes_last_address_from_end() {
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), 0) }.unwrap();
+    assert_eq!(io.address(), Some(0));
+
+    assert_eq!(io.seek(SeekFrom::Start(u64::MAX)).unwrap(), u64::MAX);
+    assert_eq!(io.address(), Some(u64::MAX));
+    assert_eq!(io.stream_position().unwrap(), u64::MAX);
+
+    assert_eq!(io.seek(SeekFrom::Current(1)).unwrap(), u64::MAX);
+    assert_eq!(io.address(), None);
+    assert_eq!(io.read(&mut [0_u8; 1]).unwrap(), 0);
+
+    assert_eq!(io.seek(SeekFrom::Current(-1)).unwrap(), u64::MAX);
+    assert_eq!(io.address(), Some(u64::MAX));
+
+    assert_eq!(io.seek(SeekFrom::End(0)).unwrap(), u64::MAX);
+    assert_eq!(io.address(), None);
+    assert_eq!(io.try_seek(SeekFrom::Start(u64::MAX)).unwrap(), u64::MAX);
+    assert_eq!(io.address(), Some(u64::MAX));
+
+    io.seek(SeekFrom::End(0)).unwrap();
+    io.set_address(u64::MAX);
+    assert_eq!(io.address(), Some(u64::MAX));
+    io.set_address(0x1000);
+    assert_eq!(io.address(), Some(0x1000));
+    assert_eq!(io.stream_position().unwrap(), 0x1000);
+}
+
+#[test]
+fn status_of_processes() {
+    let status = procfs::parse_status(
+        "Name:\tcat\n\
+         State:\tS (sleeping)\n\
+         Tgid:\t4242\n\
+         PPid:\t1\n\
+         TracerPid:\t0\n\
+         Uid:\t1000\t1001\t1000\t1000\n\
+         Gid:\t100\t101\t100\t100\n\
+         VmPeak:\t    8192 kB\n\
+         VmSize:\t    4096 kB\n\
+         VmRSS:\t     512 kB\n\
+         Threads:\t3\n\
+         SigQ:\t1/24002\n\
+         SigPnd:\t0000000000000000\n\
+         SigBlk:\t0000000000000000\n\
+         SigIgn:\t0000000000001000\n\
+         SigCgt:\t0000000000000000\n",
+    );
+    assert_eq!(status.name.as_deref(), Some("cat"));
+    assert_eq!(status.state, Some('S'));
+    assert_eq!(status.thread_group_id, Some(4242));
+    assert_eq!(status.parent_process_id, Some(1));
+    assert_eq!(status.tracer_process_id, Some(0));
+    assert_eq!((status.uid, status.effective_uid), (Some(1000), Some(1001)));
+    assert_eq!((status.gid, status.effective_gid), (Some(100), Some(101)));
+    assert_eq!(status.vm_peak, Some(8192 * 1024));
+    assert_eq!(status.vm_size, Some(4096 * 1024));
+    assert_eq!(status.vm_rss, Some(512 * 1024));
+    assert_eq!((status.vm_hwm, status.vm_swap), (None, None));
+    assert_eq!(status.threads, Some(3));
+    assert!(status.signals.unwrap().ignored.contains(libc::SIGPIPE));
+
+    assert_eq!(procfs::parse_status(""), ProcStatus::default());
+
+    let process_id = std::process::id();
+    let io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let status = io.status().unwrap();
+    assert_eq!(status.thread_group_id, Some(process_id));
+    assert_eq!(
+        status.parent_process_id,
+        Some(unsafe { libc::getppid() } as u32)
+    );
+    assert_eq!(status.uid, Some(unsafe { libc::getuid() }));
+    assert!(status.vm_size.unwrap() >= status.vm_rss.unwrap());
+    assert!(status.threads.unwrap() >= 1);
+    assert!(status.signals.is_some());
+}
+
+#[test]
+fn read_cow_borrows_from_snapshot() {
+    let process_id = std::process::id();
+    let data: Vec<u8> = (0..64).collect();
+    let address = data.as_ptr() as u64;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+
+    assert_matches!(io.read_cow(address + 8, 16).unwrap(), Cow::Owned(bytes) if bytes == data[8..24]);
+
+    io.take_snapshot(address..address + 32).unwrap();
+    assert_matches!(io.read_cow(address + 8, 16).unwrap(), Cow::Borrowed(bytes) if bytes == &data[8..24]);
+    assert_matches!(io.read_cow(address + 24, 16).unwrap(), Cow::Owned(bytes) if bytes == data[24..40]);
+    assert!(io.read_cow(0, 1).is_err());
+
+    let snapshot = io.clear_snapshot().unwrap();
+    assert_eq!(snapshot.get(address, 32), Some(&data[..32]));
+    assert_eq!(snapshot.get(address + 31, 2), None);
+    assert_eq!(snapshot.get(address - 1, 1), None);
+    assert_matches!(io.read_cow(address + 8, 16).unwrap(), Cow::Owned(_));
+    assert!(io.clear_snapshot().is_none());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn flag_self_stack_transfers() {
+    use std::sync::Mutex;
+
+    let process_id = std::process::id();
+    let heap_data = Box::new([0x5a_u8; 16]);
+    let stack_data = [0xa5_u8; 16];
+    let mut buf = [0_u8; 16];
+
+    let flags = Arc::new(Mutex::new(Vec::new()));
+    let trace_hook = || {
+        let sink = Arc::clone(&flags);
+        Box::new(move |event: TraceEvent| sink.lock().unwrap().push(event.in_caller_stack))
+    };
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    io.set_trace_hook(trace_hook());
+    io.read_large(&mut buf, heap_data.as_ptr() as u64).unwrap();
+    io.read_large(&mut buf, stack_data.as_ptr() as u64).unwrap();
+    assert_eq!(buf, stack_data);
+    assert_eq!(*flags.lock().unwrap(), [false, true]);
+
+    let mut io = unsafe {
+        ProcessVirtualMemoryIO::builder(process_id)
+            .allow_self_stack(true)
+            .build()
+    }
+    .unwrap();
+    io.set_trace_hook(trace_hook());
+    io.read_large(&mut buf, stack_data.as_ptr() as u64).unwrap();
+    assert_eq!(*flags.lock().unwrap(), [false, true, false]);
+}
+
+#[test]
+fn transfers_limited_in_bytes_per_call() {
+    use std::sync::{Arc, Mutex};
+
+    let data: Vec<u8> = (0..1000).map(|i| i as u8).collect();
+    let address = data.as_ptr() as u64;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), address) }.unwrap();
+    io.set_max_bytes_per_call(NonZeroU64::new(100).unwrap());
+    assert_eq!(io.recommended_buf_size().unwrap(), 100);
+
+    let sizes = Arc::new(Mutex::new(Vec::new()));
+    let sink = Arc::clone(&sizes);
+    io.set_trace_hook(Box::new(move |event| {
+        sink.lock().unwrap().push(event.transferred_bytes)
+    }));
+
+    let mut buf = vec![0_u8; data.len()];
+    assert_eq!(io.read(&mut buf).unwrap(), 100);
+    assert_eq!(buf[..100], data[..100]);
+    assert_eq!(io.address(), Some(address + 100));
+
+    assert_eq!(io.read_large(&mut buf, address).unwrap(), data.len());
+    assert_eq!(buf, data);
+    assert_eq!(*sizes.lock().unwrap(), [100; 11]);
+
+    // The tightest limit applies.
+    io.set_page_budget(NonZeroUsize::new(1).unwrap());
+    assert_eq!(io.recommended_buf_size().unwrap(), 100);
+    io.set_max_bytes_per_call(NonZeroU64::new(u64::MAX).unwrap());
+    assert_eq!(
+        io.recommended_buf_size().unwrap(),
+        *MIN_SYSTEM_PAGE_SIZE as usize
+    );
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn read_and_deref_pointer_table() {
+    let process_id = std::process::id();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    io.set_page_budget(NonZeroUsize::new(2).unwrap());
+
+    let values = [Box::new(11_u32), Box::new(22), Box::new(33)];
+    let table: [u64; 5] = [
+        ptr::from_ref(&*values[0]) as u64,
+        0,
+        ptr::from_ref(&*values[1]) as u64,
+        ptr::from_ref(&*values[2]) as u64,
+        8,
+    ];
+
+    let result = io
+        .read_and_deref::<u32>(table.as_ptr() as u64, table.len())
+        .unwrap();
+    assert_eq!(result.len(), table.len());
+    assert_eq!(*result[0].as_ref().unwrap(), 11);
+    assert_eq!(
+        result[1].as_ref().unwrap_err().os_error_code(),
+        Some(libc::EFAULT)
+    );
+    assert_eq!(*result[2].as_ref().unwrap(), 22);
+    assert_eq!(*result[3].as_ref().unwrap(), 33);
+    assert!(result[4].is_err());
+
+    // Errors name the system call of the backend in use.
+    assert_matches!(
+        result[1].as_ref().unwrap_err().kind(),
+        ErrorKind::Io {
+            operation: "process_vm_readv",
+            ..
+        }
+    );
+    let mut io = unsafe {
+        ProcessVirtualMemoryIO::builder(process_id)
+            .backend(Backend::ProcMem)
+            .build()
+    }
+    .unwrap();
+    let result = io
+        .read_and_deref::<u32>(table.as_ptr() as u64, table.len())
+        .unwrap();
+    assert_eq!(*result[0].as_ref().unwrap(), 11);
+    assert_matches!(
+        result[1].as_ref().unwrap_err().kind(),
+        ErrorKind::Io {
+            operation: "pread(/proc/[pid]/mem)",
+            ..
+        }
+    );
+
+    assert!(io
+        .read_and_deref::<u32>(table.as_ptr() as u64, 0)
+        .unwrap()
+        .is_empty());
+    assert!(io.read_and_deref::<u32>(0, 1).is_err());
+}
+
+#[test]
+fn last_backend_reflects_successful_transfers() {
+    let data = [0x5a_u8; 16];
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), 0) }.unwrap();
+    assert_eq!(io.last_backend(), None);
+
+    let mut buf = [0_u8; 16];
+    assert!(io.read(&mut buf).is_err());
+    assert_eq!(io.last_backend(), None);
+
+    io.read_large(&mut buf, data.as_ptr() as u64).unwrap();
+    assert_eq!(io.last_backend(), Some(Backend::Syscall));
+
+    assert!(io.read_large(&mut buf, 0).is_err());
+    assert_eq!(io.last_backend(), Some(Backend::Syscall));
+}
+
+#[test]
+fn transfer_single_io_slices() {
+    let mut data = [1_u8, 2, 3, 4, 5, 6, 7, 8];
+    let address = data.as_ptr() as u64;
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), address) }.unwrap();
+
+    let mut buf = [0_u8; 4];
+    assert_eq!(io.read_io_slice(&mut IoSliceMut::new(&mut buf)).unwrap(), 4);
+    assert_eq!(buf, data[..4]);
+    assert_eq!(io.address(), Some(address + 4));
+
+    assert_eq!(io.write_io_slice(&IoSlice::new(&[9, 9])).unwrap(), 2);
+    data = std::hint::black_box(data);
+    assert_eq!(data, [1, 2, 3, 4, 9, 9, 7, 8]);
+    assert_eq!(io.address(), Some(address + 6));
+
+    io.set_address(0);
+    assert!(io.read_io_slice(&mut IoSliceMut::new(&mut buf)).is_err());
+}
+
+#[test]
+fn probe_capabilities_of_processes() {
+    let capabilities = probe_capabilities(std::process::id());
+    assert!(capabilities.process_vm_readv);
+    assert!(capabilities.proc_mem);
+    assert!(capabilities.pidfd);
+    // A process cannot trace itself.
+    assert!(!capabilities.ptrace);
+
+    // A terminated process.
+    let child_id = unsafe { libc::fork() };
+    assert_ne!(child_id, -1);
+    if child_id == 0 {
+        unsafe { libc::_exit(0) };
+    }
+    assert_eq!(
+        unsafe { libc::waitpid(child_id, ptr::null_mut(), 0) },
+        child_id
+    );
+    assert_eq!(probe_capabilities(child_id as u32), Capabilities::default());
+
+    assert_eq!(probe_capabilities(0), Capabilities::default());
+    assert_eq!(probe_capabilities(u32::MAX), Capabilities::default());
+}
+
+#[test]
+fn invalid_address_errors_on_first_byte_only() {
+    let process_id = std::process::id();
+    let page_size = system_page_size().unwrap().get() as usize;
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 2,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    assert_eq!(
+        unsafe { libc::munmap(mapping.cast::<u8>().add(page_size).cast(), page_size) },
+        0
+    );
+    let start = mapping as u64;
+    let hole = start + page_size as u64;
+    let mut buf = vec![0_u8; page_size * 2];
+
+    // Disabled by default.
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, hole) }.unwrap();
+    let err = io.read_large(&mut buf, hole).unwrap_err();
+    assert_matches!(err.kind(), ErrorKind::Io { error, .. } if error.raw_os_error() == Some(libc::EFAULT));
+
+    let mut io = unsafe {
+        ProcessVirtualMemoryIO::builder(process_id)
+            .initial_address(hole)
+            .invalid_address_errors(true)
+            .build()
+    }
+    .unwrap();
+
+    let err = io.read_large(&mut buf, hole).unwrap_err();
+    assert_matches!(err.kind(), ErrorKind::InvalidAddress { address, .. } if *address == hole);
+    assert_eq!(err.os_error_code(), Some(libc::EFAULT));
+    assert_eq!(err.error_category(), ErrorCategory::Os(libc::EFAULT));
+    assert!(err.to_string().contains(&format!("{hole:#x}")));
+
+    let err = io.read_raw(&mut buf).unwrap_err();
+    assert_matches!(err.kind(), ErrorKind::InvalidAddress { address, .. } if *address == hole);
+
+    let err = io.write_large(&buf, hole).unwrap_err();
+    assert_matches!(err.kind(), ErrorKind::InvalidAddress { address, .. } if *address == hole);
+
+    // A fault after some data was transferred is reported as is.
+    assert_eq!(io.read_large(&mut buf, start).unwrap(), page_size);
+    let err = io
+        .read_exact_at_into(&mut Vec::new(), page_size * 2, start)
+        .unwrap_err();
+    assert_matches!(err.kind(), ErrorKind::Io { .. });
+
+    unsafe { libc::munmap(mapping, page_size) };
+}
+
+#[test]
+fn transform_region_stops_at_first_fault() {
+    let process_id = std::process::id();
+    let page_size = system_page_size().unwrap().get() as usize;
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 3,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let bytes = unsafe { slice::from_raw_parts_mut(mapping as *mut u8, page_size * 2) };
+    bytes.fill(0x0f);
+    assert_eq!(
+        unsafe { libc::munmap(mapping.cast::<u8>().add(page_size * 2).cast(), page_size) },
+        0
+    );
+    let start = mapping as u64;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    io.set_max_bytes_per_call(NonZeroU64::new(page_size as u64).unwrap());
+
+    // Chunks are aligned on page boundaries, and the hole stops transforming.
+    let mut chunks = Vec::new();
+    let count = io
+        .transform_region(
+            start + 16..start + (page_size * 3) as u64,
+            |address, data| {
+                chunks.push((address, data.len()));
+                data.iter_mut().for_each(|byte| *byte ^= 0xff);
+            },
+        )
+        .unwrap();
+    assert_eq!(count, (page_size * 2 - 16) as u64);
+    assert_eq!(
+        chunks,
+        [
+            (start + 16, page_size - 16),
+            (start + page_size as u64, page_size)
+        ]
+    );
+    assert!(bytes[..16].iter().all(|&b| b == 0x0f));
+    assert!(bytes[16..].iter().all(|&b| b == 0xf0));
+
+    // A read-only page stops transforming, without writing its data back.
+    assert_eq!(
+        unsafe {
+            libc::mprotect(
+                mapping.cast::<u8>().add(page_size).cast(),
+                page_size,
+                libc::PROT_READ,
+            )
+        },
+        0
+    );
+    let count = io
+        .transform_region(start..start + (page_size * 2) as u64, |_address, data| {
+            data.fill(0)
+        })
+        .unwrap();
+    assert_eq!(count, page_size as u64);
+    assert!(bytes[..page_size].iter().all(|&b| b == 0));
+    assert!(bytes[page_size..].iter().all(|&b| b == 0xf0));
+
+    // When checking writability, the read-only page is rejected before being
+    // transformed.
+    io.set_check_writability(true);
+    let mut chunks = Vec::new();
+    let count = io
+        .transform_region(start..start + (page_size * 2) as u64, |address, data| {
+            chunks.push(address);
+            data.fill(1)
+        })
+        .unwrap();
+    assert_eq!(count, page_size as u64);
+    assert_eq!(chunks, [start]);
+    assert!(bytes[..page_size].iter().all(|&b| b == 1));
+    assert!(bytes[page_size..].iter().all(|&b| b == 0xf0));
+
+    let read_only = start + page_size as u64;
+    assert_matches!(
+        io.transform_region(read_only..read_only + 16, |_, _| unreachable!())
+            .unwrap_err()
+            .kind(),
+        ErrorKind::NotWritable { address, .. } if *address == read_only
+    );
+    io.set_check_writability(false);
+
+    assert_eq!(io.transform_region(start..start, |_, _| ()).unwrap(), 0);
+    assert!(io.transform_region(start + 1..start, |_, _| ()).is_err());
+
+    unsafe { libc::munmap(mapping, page_size * 2) };
+}
+
+#[test]
+fn positioned_transfers_keep_current_address() {
+    let process_id = std::process::id();
+    let mut data = b"positioned".to_vec();
+    let address = data.as_mut_ptr() as u64;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0x1000) }.unwrap();
+    let mut buf = [0_u8; 10];
+    assert_eq!(io.read_at(address, &mut buf).unwrap(), 10);
+    assert_eq!(&buf, b"positioned");
+    assert_eq!(io.address(), Some(0x1000));
+
+    assert_eq!(io.write_at(address, b"POS").unwrap(), 3);
+    assert_eq!(data, b"POSitioned");
+    assert_eq!(io.address(), Some(0x1000));
+
+    assert_eq!(io.read_at(address, &mut []).unwrap(), 0);
+    assert_eq!(
+        io.read_at(0, &mut buf).unwrap_err().os_error_code(),
+        Some(libc::EFAULT)
+    );
+    assert_eq!(io.address(), Some(0x1000));
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn pod_values_roundtrip() {
+    let process_id = std::process::id();
+    let page_size = system_page_size().unwrap().get() as usize;
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 2,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    assert_eq!(
+        unsafe { libc::munmap(mapping.cast::<u8>().add(page_size).cast(), page_size) },
+        0
+    );
+    let start = mapping as u64;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    let value: [u32; 3] = [1, 0xdead_beef, 3];
+    io.write_pod(start + 8, &value).unwrap();
+    assert_eq!(io.read_pod::<[u32; 3]>(start + 8).unwrap(), value);
+    assert_eq!(io.read_pod::<u32>(start + 12).unwrap(), 0xdead_beef);
+    assert_eq!(io.address, Some(0));
+
+    // A value straddling the end of the mapping is not read partially.
+    let end = start + page_size as u64;
+    io.write_pod(end - 4, &7_u32).unwrap();
+    assert!(io.read_pod::<u64>(end - 4).is_err());
+    assert!(io.write_pod(end - 4, &7_u64).is_err());
+
+    unsafe { libc::munmap(mapping, page_size) };
+}
+
+#[test]
+fn buffer_pool_reuses_scratch_buffers() {
+    let process_id = std::process::id();
+    let page_size = *MIN_SYSTEM_PAGE_SIZE as usize;
+
+    let text: Vec<u8> = (0..page_size * 2 + 3)
+        .map(|i| b'a' + (i % 26) as u8)
+        .collect();
+    let mut c_string = text.clone();
+    c_string.push(0);
+    let address = c_string.as_ptr() as u64;
+
+    let pool = Arc::new(BufferPool::new(1));
+    let mut io = unsafe {
+        ProcessVirtualMemoryIO::builder(process_id)
+            .buffer_pool(Arc::clone(&pool))
+            .build()
+    }
+    .unwrap();
+    assert_eq!(pool.idle_count(), 0);
+
+    for _ in 0..2 {
+        assert_eq!(
+            io.read_cstr(address, usize::MAX).unwrap(),
+            RemoteString::Terminated(text.clone())
+        );
+        assert_eq!(pool.idle_count(), 1);
+    }
+    assert_eq!(
+        io.read_cstr(address + 1, 4).unwrap(),
+        RemoteString::Truncated(text[1..5].to_vec())
+    );
+    assert!(io.read_cstr(0, 4).is_err());
+    assert_eq!(pool.idle_count(), 1);
+
+    let region = MemoryRegion {
+        start: address,
+        end: address + text.len() as u64,
+        ..io.region_containing(address).unwrap().unwrap().clone()
+    };
+    let mut out = Vec::new();
+    assert_eq!(
+        io.dump_region(&region, &mut out).unwrap(),
+        text.len() as u64
+    );
+    assert_eq!(out, text);
+    assert_eq!(pool.idle_count(), 1);
+
+    // Buffers are not kept beyond the capacity of the pool.
+    let pool = Arc::new(BufferPool::new(0));
+    io.set_buffer_pool(Some(Arc::clone(&pool)));
+    assert_eq!(io.read_cstr(address, 4).unwrap().into_inner(), &text[..4]);
+    assert_eq!(pool.idle_count(), 0);
+
+    io.set_buffer_pool(None);
+    assert_eq!(io.read_cstr(address, 4).unwrap().into_inner(), &text[..4]);
+}
+
+#[test]
+fn read_cstr_stops_before_unmapped_page() {
+    let process_id = std::process::id();
+    let page_size = system_page_size().unwrap().get() as usize;
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 2,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    assert_eq!(
+        unsafe { libc::munmap(mapping.cast::<u8>().add(page_size).cast(), page_size) },
+        0
+    );
+    let bytes = unsafe { slice::from_raw_parts_mut(mapping as *mut u8, page_size) };
+    let end = mapping as u64 + page_size as u64;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    bytes[page_size - 6..].copy_from_slice(b"hello\0");
+    let string = io.read_cstr(end - 6, 100).unwrap();
+    assert_eq!(string, RemoteString::Terminated(b"hello".to_vec()));
+    assert!(string.is_terminated());
+    assert_eq!(io.read_cstr(end - 1, 100).unwrap().as_slice(), b"");
+
+    let string = io.read_cstr(end - 6, 3).unwrap();
+    assert_eq!(string, RemoteString::Truncated(b"hel".to_vec()));
+    assert!(!string.is_terminated());
+    assert_eq!(io.read_cstr(end - 6, 5).unwrap().into_inner(), b"hello");
+    assert_eq!(
+        io.read_cstr(end - 6, 0).unwrap(),
+        RemoteString::Truncated(Vec::new())
+    );
+
+    // No NUL terminator before the unmapped page.
+    bytes[page_size - 1] = b'!';
+    assert_eq!(
+        io.read_cstr(end - 6, 100).unwrap_err().os_error_code(),
+        Some(libc::EFAULT)
+    );
+    assert_eq!(
+        io.read_cstr(end - 6, 6).unwrap(),
+        RemoteString::Truncated(b"hello!".to_vec())
+    );
+
+    // A string spanning several pages.
+    let text: Vec<u8> = (0..page_size * 3).map(|i| b'a' + (i % 26) as u8).collect();
+    let mut c_string = text.clone();
+    c_string.push(0);
+    assert_eq!(
+        io.read_cstr(c_string.as_ptr() as u64, usize::MAX).unwrap(),
+        RemoteString::Terminated(text)
+    );
+
+    unsafe { libc::munmap(mapping, page_size) };
+}
+
+#[test]
+fn read_utf16_strings_of_both_byte_orders() {
+    let process_id = std::process::id();
+    let page_size = system_page_size().unwrap().get() as usize;
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 2,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    assert_eq!(
+        unsafe { libc::munmap(mapping.cast::<u8>().add(page_size).cast(), page_size) },
+        0
+    );
+    let bytes = unsafe { slice::from_raw_parts_mut(mapping as *mut u8, page_size) };
+    let end = mapping as u64 + page_size as u64;
+
+    // "hé" in little endian, ending at the unmapped page.
+    bytes[page_size - 6..].copy_from_slice(&[b'h', 0, 0xe9, 0, 0, 0]);
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    io.set_endianness(Endianness::Little);
+    assert_eq!(
+        io.read_utf16_cstr(end - 6, 10).unwrap(),
+        RemoteString::Terminated(vec![0x68, 0xe9])
+    );
+    assert_eq!(io.read_utf16_string(end - 6, 10).unwrap(), "hé");
+    assert_eq!(
+        io.read_utf16_cstr(end - 6, 1).unwrap(),
+        RemoteString::Truncated(vec![0x68])
+    );
+
+    io.set_endianness(Endianness::Big);
+    assert_eq!(
+        io.read_utf16_cstr(end - 6, 10).unwrap(),
+        RemoteString::Terminated(vec![0x6800, 0xe900])
+    );
+
+    // A NUL code unit straddling a page boundary, and an unpaired surrogate.
+    let units: Vec<u16> = vec![0xd800, 0x0041, 0];
+    let mut data: Vec<u8> = vec![0xff; page_size * 3];
+    let offset = page_size - (data.as_ptr() as usize % page_size) + page_size - 5;
+    for (index, unit) in units.iter().enumerate() {
+        data[offset + index * 2..offset + index * 2 + 2].copy_from_slice(&unit.to_be_bytes());
+    }
+    let address = data.as_ptr() as u64 + offset as u64;
+    assert_eq!(
+        io.read_utf16_cstr(address, usize::MAX).unwrap(),
+        RemoteString::Terminated(units[..2].to_vec())
+    );
+    assert_eq!(
+        io.read_utf16_string(address, usize::MAX).unwrap(),
+        "\u{fffd}A"
+    );
+
+    unsafe { libc::munmap(mapping, page_size) };
+}
+
+#[test]
+fn read_vectored_report_tells_coverage_limits() {
+    let process_id = std::process::id();
+    let page_size = system_page_size().unwrap().get() as usize;
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 4,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let start = mapping as u64;
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, start) }.unwrap();
+    io.set_page_budget(NonZeroUsize::new(2).unwrap());
+    let (mut first, mut second) = (vec![0_u8; page_size], vec![0_u8; page_size * 3]);
+    let report = io
+        .read_vectored_report(&mut [IoSliceMut::new(&mut first), IoSliceMut::new(&mut second)])
+        .unwrap();
+    assert_eq!(report.transferred, page_size * 2);
+    assert!(report.coverage_limited);
+    assert_eq!(report.uncovered, (page_size * 2) as u64);
+    assert_eq!(io.address(), Some(start + (page_size * 2) as u64));
+
+    let report = io
+        .read_vectored_report(&mut [IoSliceMut::new(&mut second[..page_size * 2])])
+        .unwrap();
+    assert_eq!(report.transferred, page_size * 2);
+    assert!(!report.coverage_limited);
+    assert_eq!(report.uncovered, 0);
+
+    io.set_address(start);
+    io.set_max_bytes_per_call(NonZeroU64::new(100).unwrap());
+    let report = io
+        .read_vectored_report(&mut [IoSliceMut::new(&mut first)])
+        .unwrap();
+    assert_eq!(report.transferred, 100);
+    assert!(report.coverage_limited);
+    assert_eq!(report.uncovered, (page_size - 100) as u64);
+
+    assert_eq!(
+        io.read_vectored_report(&mut []).unwrap(),
+        TransferReport::default()
+    );
+
+    unsafe { libc::munmap(mapping, page_size * 4) };
+}
+
+#[test]
+fn io_errors_keep_their_kind() {
+    // A terminated process.
+    let child_id = unsafe { libc::fork() };
+    assert_ne!(child_id, -1);
+    if child_id == 0 {
+        unsafe { libc::_exit(0) };
+    }
+    assert_eq!(
+        unsafe { libc::waitpid(child_id, ptr::null_mut(), 0) },
+        child_id
+    );
+
+    let mut buf = [0_u8; 1];
+    let mut io = unsafe { ProcessVirtualMemoryIO::new_unchecked(child_id as u32, 0x1000) };
+    let err = io.read(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    assert_eq!(
+        err.get_ref()
+            .unwrap()
+            .downcast_ref::<Error>()
+            .unwrap()
+            .os_error_code(),
+        Some(libc::ESRCH)
+    );
+    assert_eq!(io.write(&buf).unwrap_err().kind(), io::ErrorKind::NotFound);
+
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(std::process::id(), 0) }.unwrap();
+    assert_eq!(
+        io.read(&mut buf).unwrap_err().kind(),
+        io::Error::from_raw_os_error(libc::EFAULT).kind()
+    );
+
+    let err = io::Error::from(Error::from(ErrorKind::TooManyVMPages));
+    assert_eq!(err.kind(), io::ErrorKind::Other);
+}
+
+#[test]
+fn proc_mem_fallback_transfers() {
+    let process_id = std::process::id();
+    let page_size = system_page_size().unwrap().get() as usize;
+    let mapping = unsafe {
+        libc::mmap(
+            ptr::null_mut(),
+            page_size * 3,
+            libc::PROT_READ | libc::PROT_WRITE,
+            libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+            -1,
+            0,
+        )
+    };
+    assert_ne!(mapping, libc::MAP_FAILED);
+    let bytes = unsafe { slice::from_raw_parts_mut(mapping as *mut u8, page_size * 2) };
+    bytes
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, b)| *b = (i % 251) as u8);
+    assert_eq!(
+        unsafe { libc::munmap(mapping.cast::<u8>().add(page_size * 2).cast(), page_size) },
+        0
+    );
+    let start = mapping as u64;
+
+    // As if the system did not support `process_vm_readv()`.
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, start + 5) }.unwrap();
+    io.proc_mem_fallback.store(true, atomic::Ordering::Relaxed);
+
+    // Scattered into several local buffers, and stopping at the hole.
+    let (mut first, mut second) = (vec![0_u8; 7], vec![0_u8; page_size * 3]);
+    let count = io
+        .read_vectored(&mut [IoSliceMut::new(&mut first), IoSliceMut::new(&mut second)])
+        .unwrap();
+    assert_eq!(count, page_size * 2 - 5);
+    assert_eq!(first, bytes[5..12]);
+    assert_eq!(second[..count - 7], bytes[12..]);
+    assert_eq!(io.last_backend(), Some(Backend::ProcMem));
+
+    let err = io.read_raw(&mut first).unwrap_err();
+    assert_eq!(err.os_error_code(), Some(libc::EFAULT));
+    assert_matches!(err.kind(), ErrorKind::Io { operation, .. } if operation.contains("/proc/[pid]/mem"));
+
+    assert_eq!(
+        io.write_large(b"fallback", start + page_size as u64 - 2)
+            .unwrap(),
+        8
+    );
+    assert_eq!(&bytes[page_size - 2..page_size + 6], b"fallback");
+
+    io.set_page_budget(NonZeroUsize::new(1).unwrap());
+    assert_eq!(io.read_at(start + 1, &mut second).unwrap(), page_size - 1);
+    assert_eq!(io.last_backend(), Some(Backend::ProcMem));
+
+    unsafe { libc::munmap(mapping, page_size * 2) };
+}
+
+#[test]
+fn backend_resolution() {
+    assert!(LIBC_PROCESS_VM_READV.is_some());
+    assert!(LIBC_PROCESS_VM_WRITEV.is_some());
+    assert!(unsafe { resolve_libc_function(c"process_vm_io_no_such_function") }.is_none());
+
+    let process_id = std::process::id();
+    let data = b"backend".to_vec();
+    let mut io = unsafe { ProcessVirtualMemoryIO::new(process_id, 0) }.unwrap();
+    assert_eq!(io.backend(), Backend::Syscall);
+
+    // The system call, issued without the C library.
+    let mut buf = [0_u8; 7];
+    let local_io_vector = libc::iovec {
+        iov_base: buf.as_mut_ptr().cast(),
+        iov_len: buf.len(),
+    };
+    let remote_io_vector = libc::iovec {
+        iov_base: data.as_ptr() as *mut c_void,
+        iov_len: data.len(),
+    };
+    let count = unsafe {
+        process_vm_syscall(
+            libc::SYS_process_vm_readv,
+            process_id as libc::pid_t,
+            &local_io_vector,
+            1,
+            &remote_io_vector,
+            1,
+            0,
+        )
+    };
+    assert_eq!(count, 7);
+    assert_eq!(&buf, b"backend");
+
+    io.proc_mem_fallback.store(true, atomic::Ordering::Relaxed);
+    assert_eq!(io.backend(), Backend::ProcMem);
+    assert_eq!(io.read_at(data.as_ptr() as u64, &mut buf).unwrap(), 7);
+    assert_eq!(io.last_backend(), Some(Backend::ProcMem));
+}
@@ -0,0 +1,74 @@
+// Copyright (c) 2020-2024 MicroDoc Software GmbH.
+// See the "LICENSE.txt" file at the top-level directory of this distribution.
+//
+// Licensed under the MIT license. This file may not be copied, modified,
+// or distributed except according to those terms.
+
+/*! Tracing of data transfers. */
+
+use std::fmt;
+use std::os::raw::c_int;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Description of a data transfer from/to the virtual memory of a process, as
+/// provided to the hook set by
+/// [`ProcessVirtualMemoryIO::set_trace_hook`](crate::ProcessVirtualMemoryIO::set_trace_hook).
+///
+/// A transfer is usually one system call. A transfer of more than `IOV_MAX`
+/// buffers or address ranges is split into batches, one system call per
+/// batch, which are described together by one event.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct TraceEvent {
+    /// Name of the system call(s), e.g., `process_vm_readv`.
+    pub operation: &'static str,
+    /// Identifier of the target process.
+    pub process_id: u32,
+    /// Address of the first byte to transfer in the target process.
+    pub address: u64,
+    /// Number of bytes requested to be transferred.
+    ///
+    /// This is the size of the address range(s) covered by the transfer,
+    /// which can be less than what the caller of this crate requested: a
+    /// transfer covers at most a limited number of pages, at most the
+    /// [maximum bytes per call](crate::ProcessVirtualMemoryIO::set_max_bytes_per_call),
+    /// and not beyond the end of the address space.
+    pub requested_bytes: u64,
+    /// Number of bytes actually transferred.
+    pub transferred_bytes: usize,
+    /// Error code reported by the transfer, if it failed.
+    pub errno: Option<c_int>,
+    /// Time spent in the system call(s).
+    pub duration: Duration,
+    /// Whether the transferred address range overlaps the stack of the
+    /// calling thread, in the current process.
+    ///
+    /// That stack changes as the calling thread runs, including during the
+    /// transfer, so such transfers are almost always a mistake, which the
+    /// hook can report. This is only detected in debug builds, unless allowed
+    /// by [`Builder::allow_self_stack`](crate::Builder::allow_self_stack).
+    pub in_caller_stack: bool,
+}
+
+/// Hook called after each data transfer.
+pub(crate) struct TraceHook(Mutex<Box<dyn FnMut(TraceEvent) + Send>>);
+
+impl TraceHook {
+    pub(crate) fn new(hook: Box<dyn FnMut(TraceEvent) + Send>) -> Self {
+        Self(Mutex::new(hook))
+    }
+
+    /// Call the hook with the given event.
+    pub(crate) fn call(&self, event: TraceEvent) {
+        // A panic in a previous call does not prevent further calls.
+        let mut hook = self.0.lock().unwrap_or_else(|err| err.into_inner());
+        hook(event)
+    }
+}
+
+impl fmt::Debug for TraceHook {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TraceHook").finish_non_exhaustive()
+    }
+}